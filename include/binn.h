@@ -0,0 +1,993 @@
+#ifndef BINN_H
+#define BINN_H
+
+/* Generated with cbindgen, do not edit manually */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+#ifndef BOOL
+typedef int BOOL;
+#endif
+
+#ifndef TRUE
+#define TRUE 1
+#endif
+
+#ifndef FALSE
+#define FALSE 0
+#endif
+
+/* Container created with binn_list, binn_map or binn_object */
+typedef struct binn binn;
+
+/**
+ * Type of list container, as returned by [`binn_type`]
+ */
+#define BINN_LIST 224
+
+/**
+ * Type of map container, as returned by [`binn_type`]
+ */
+#define BINN_MAP 225
+
+/**
+ * Type of object container, as returned by [`binn_type`]
+ */
+#define BINN_OBJECT 226
+
+/**
+ * Creates new empty list
+ */
+struct binn *binn_list(void);
+
+/**
+ * Creates new empty map
+ */
+struct binn *binn_map(void);
+
+/**
+ * Creates new empty object
+ */
+struct binn *binn_object(void);
+
+/**
+ * Releases container created by this module, does nothing for other pointers
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+void binn_free(struct binn *item);
+
+/**
+ * Returns pointer to serialized container or null if it is not valid
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+void *binn_ptr(void *ptr);
+
+/**
+ * Returns size of serialized container or -1 if it is not valid
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_size(void *ptr);
+
+/**
+ * Returns number of items in container or -1 if it is not valid
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_count(void *ptr);
+
+/**
+ * Returns type of container ([`BINN_LIST`], [`BINN_MAP`] or [`BINN_OBJECT`])
+ * or -1 if it is not valid
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_type(void *ptr);
+
+/**
+ * Adds null value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_null(struct binn *list);
+
+/**
+ * Adds null value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_null(struct binn *map, int id);
+
+/**
+ * Adds null value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_null(struct binn *obj, const char *key);
+
+/**
+ * Adds blob of given size to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_blob(struct binn *list, void *ptr, int size);
+
+/**
+ * Adds blob of given size with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_blob(struct binn *map, int id, void *ptr, int size);
+
+/**
+ * Adds blob of given size with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_blob(struct binn *obj, const char *key, void *ptr, int size);
+
+/**
+ * Reads blob at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_blob(void *list, int pos, void **pvalue, int *psize);
+
+/**
+ * Reads blob with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_blob(void *map, int id, void **pvalue, int *psize);
+
+/**
+ * Reads blob with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_blob(void *obj, const char *key, void **pvalue, int *psize);
+
+/**
+ * Adds `int8` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_int8(struct binn *list, int8_t value);
+
+/**
+ * Adds `int8` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_int8(struct binn *map, int id, int8_t value);
+
+/**
+ * Adds `int8` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_int8(struct binn *obj, const char *key, int8_t value);
+
+/**
+ * Reads `int8` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_int8(void *list, int pos, int8_t *pvalue);
+
+/**
+ * Reads `int8` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_int8(void *map, int id, int8_t *pvalue);
+
+/**
+ * Reads `int8` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_int8(void *obj, const char *key, int8_t *pvalue);
+
+/**
+ * Adds `int16` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_int16(struct binn *list, int16_t value);
+
+/**
+ * Adds `int16` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_int16(struct binn *map, int id, int16_t value);
+
+/**
+ * Adds `int16` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_int16(struct binn *obj, const char *key, int16_t value);
+
+/**
+ * Reads `int16` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_int16(void *list, int pos, int16_t *pvalue);
+
+/**
+ * Reads `int16` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_int16(void *map, int id, int16_t *pvalue);
+
+/**
+ * Reads `int16` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_int16(void *obj, const char *key, int16_t *pvalue);
+
+/**
+ * Adds `int32` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_int32(struct binn *list, int32_t value);
+
+/**
+ * Adds `int32` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_int32(struct binn *map, int id, int32_t value);
+
+/**
+ * Adds `int32` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_int32(struct binn *obj, const char *key, int32_t value);
+
+/**
+ * Reads `int32` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_int32(void *list, int pos, int32_t *pvalue);
+
+/**
+ * Reads `int32` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_int32(void *map, int id, int32_t *pvalue);
+
+/**
+ * Reads `int32` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_int32(void *obj, const char *key, int32_t *pvalue);
+
+/**
+ * Adds `int64` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_int64(struct binn *list, int64_t value);
+
+/**
+ * Adds `int64` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_int64(struct binn *map, int id, int64_t value);
+
+/**
+ * Adds `int64` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_int64(struct binn *obj, const char *key, int64_t value);
+
+/**
+ * Reads `int64` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_int64(void *list, int pos, int64_t *pvalue);
+
+/**
+ * Reads `int64` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_int64(void *map, int id, int64_t *pvalue);
+
+/**
+ * Reads `int64` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_int64(void *obj, const char *key, int64_t *pvalue);
+
+/**
+ * Adds `uint8` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_uint8(struct binn *list, uint8_t value);
+
+/**
+ * Adds `uint8` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_uint8(struct binn *map, int id, uint8_t value);
+
+/**
+ * Adds `uint8` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_uint8(struct binn *obj, const char *key, uint8_t value);
+
+/**
+ * Reads `uint8` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_uint8(void *list, int pos, uint8_t *pvalue);
+
+/**
+ * Reads `uint8` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_uint8(void *map, int id, uint8_t *pvalue);
+
+/**
+ * Reads `uint8` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_uint8(void *obj, const char *key, uint8_t *pvalue);
+
+/**
+ * Adds `uint16` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_uint16(struct binn *list, uint16_t value);
+
+/**
+ * Adds `uint16` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_uint16(struct binn *map, int id, uint16_t value);
+
+/**
+ * Adds `uint16` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_uint16(struct binn *obj, const char *key, uint16_t value);
+
+/**
+ * Reads `uint16` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_uint16(void *list, int pos, uint16_t *pvalue);
+
+/**
+ * Reads `uint16` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_uint16(void *map, int id, uint16_t *pvalue);
+
+/**
+ * Reads `uint16` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_uint16(void *obj, const char *key, uint16_t *pvalue);
+
+/**
+ * Adds `uint32` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_uint32(struct binn *list, uint32_t value);
+
+/**
+ * Adds `uint32` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_uint32(struct binn *map, int id, uint32_t value);
+
+/**
+ * Adds `uint32` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_uint32(struct binn *obj, const char *key, uint32_t value);
+
+/**
+ * Reads `uint32` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_uint32(void *list, int pos, uint32_t *pvalue);
+
+/**
+ * Reads `uint32` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_uint32(void *map, int id, uint32_t *pvalue);
+
+/**
+ * Reads `uint32` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_uint32(void *obj, const char *key, uint32_t *pvalue);
+
+/**
+ * Adds `uint64` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_uint64(struct binn *list, uint64_t value);
+
+/**
+ * Adds `uint64` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_uint64(struct binn *map, int id, uint64_t value);
+
+/**
+ * Adds `uint64` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_uint64(struct binn *obj, const char *key, uint64_t value);
+
+/**
+ * Reads `uint64` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_uint64(void *list, int pos, uint64_t *pvalue);
+
+/**
+ * Reads `uint64` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_uint64(void *map, int id, uint64_t *pvalue);
+
+/**
+ * Reads `uint64` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_uint64(void *obj, const char *key, uint64_t *pvalue);
+
+/**
+ * Adds `float` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_float(struct binn *list, float value);
+
+/**
+ * Adds `float` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_float(struct binn *map, int id, float value);
+
+/**
+ * Adds `float` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_float(struct binn *obj, const char *key, float value);
+
+/**
+ * Reads `float` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_float(void *list, int pos, float *pvalue);
+
+/**
+ * Reads `float` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_float(void *map, int id, float *pvalue);
+
+/**
+ * Reads `float` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_float(void *obj, const char *key, float *pvalue);
+
+/**
+ * Adds `double` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_double(struct binn *list, double value);
+
+/**
+ * Adds `double` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_double(struct binn *map, int id, double value);
+
+/**
+ * Adds `double` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_double(struct binn *obj, const char *key, double value);
+
+/**
+ * Reads `double` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_double(void *list, int pos, double *pvalue);
+
+/**
+ * Reads `double` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_double(void *map, int id, double *pvalue);
+
+/**
+ * Reads `double` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_double(void *obj, const char *key, double *pvalue);
+
+/**
+ * Adds `bool` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_bool(struct binn *list, int value);
+
+/**
+ * Adds `bool` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_bool(struct binn *map, int id, int value);
+
+/**
+ * Adds `bool` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_bool(struct binn *obj, const char *key, int value);
+
+/**
+ * Reads `bool` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_bool(void *list, int pos, int *pvalue);
+
+/**
+ * Reads `bool` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_bool(void *map, int id, int *pvalue);
+
+/**
+ * Reads `bool` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_bool(void *obj, const char *key, int *pvalue);
+
+/**
+ * Adds `str` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_str(struct binn *list, char *value);
+
+/**
+ * Adds `str` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_str(struct binn *map, int id, char *value);
+
+/**
+ * Adds `str` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_str(struct binn *obj, const char *key, char *value);
+
+/**
+ * Reads `str` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_str(void *list, int pos, char **pvalue);
+
+/**
+ * Reads `str` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_str(void *map, int id, char **pvalue);
+
+/**
+ * Reads `str` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_str(void *obj, const char *key, char **pvalue);
+
+/**
+ * Adds `list` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_list(struct binn *list, void *value);
+
+/**
+ * Adds `list` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_list(struct binn *map, int id, void *value);
+
+/**
+ * Adds `list` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_list(struct binn *obj, const char *key, void *value);
+
+/**
+ * Reads `list` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_list(void *list, int pos, void **pvalue);
+
+/**
+ * Reads `list` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_list(void *map, int id, void **pvalue);
+
+/**
+ * Reads `list` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_list(void *obj, const char *key, void **pvalue);
+
+/**
+ * Adds `map` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_map(struct binn *list, void *value);
+
+/**
+ * Adds `map` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_map(struct binn *map, int id, void *value);
+
+/**
+ * Adds `map` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_map(struct binn *obj, const char *key, void *value);
+
+/**
+ * Reads `map` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_map(void *list, int pos, void **pvalue);
+
+/**
+ * Reads `map` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_map(void *map, int id, void **pvalue);
+
+/**
+ * Reads `map` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_map(void *obj, const char *key, void **pvalue);
+
+/**
+ * Adds `object` value to the end of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_add_object(struct binn *list, void *value);
+
+/**
+ * Adds `object` value with given id to map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_set_object(struct binn *map, int id, void *value);
+
+/**
+ * Adds `object` value with given key to object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_set_object(struct binn *obj, const char *key, void *value);
+
+/**
+ * Reads `object` value at given position (starting from 1) of list
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_list_get_object(void *list, int pos, void **pvalue);
+
+/**
+ * Reads `object` value with given id from map
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_map_get_object(void *map, int id, void **pvalue);
+
+/**
+ * Reads `object` value with given key from object
+ *
+ * # Safety
+ *
+ * See [module documentation](self)
+ */
+int binn_object_get_object(void *obj, const char *key, void **pvalue);
+
+#endif  /* BINN_H */
@@ -0,0 +1,516 @@
+//! Python bindings for binn-rs
+//!
+//! Module `binn_rs` provides `loads` and `dumps` functions that convert between
+//! binn documents and Python objects:
+//!
+//! | binn                                             | Python                 |
+//! |--------------------------------------------------|------------------------|
+//! | `Null`                                           | `None`                 |
+//! | `True`, `False`                                  | `bool`                 |
+//! | integers                                         | `int`                  |
+//! | `Float`, `Double`                                | `float`                |
+//! | `Text`, `DateTime`, `Date`, `Time`, `DecimalStr` | `str`                  |
+//! | `Blob`                                           | `bytes`                |
+//! | `List`                                           | `list`                 |
+//! | `Map`                                            | `dict` with `int` keys |
+//! | `Object`                                         | `dict` with `str` keys |
+//! | user-defined types                               | `UserValue`            |
+//!
+//! Integers are written with the smallest type that can hold them,
+//! `float` is written as `Double`. `dict` is written as `Map` when all its keys
+//! are `int` and as `Object` otherwise (so empty `dict` is always an `Object`).
+#![warn(rust_2018_idioms, missing_debug_implementations, missing_docs)]
+
+use binn_rs::{Error, List, Map, Object, Storage, SubType, Value};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use pyo3::IntoPyObjectExt;
+
+/// Initial size of buffer for serialized containers
+const INITIAL_SIZE: usize = 256;
+
+create_exception!(
+    binn_rs,
+    BinnError,
+    PyValueError,
+    "Data is not a valid binn document or can't be written as one"
+);
+
+/// Value of user-defined type
+///
+/// `storage` is one of `"nobytes"`, `"byte"`, `"word"`, `"dword"`, `"qword"`,
+/// `"string"`, `"blob"` or `"container"`. `value` is `None`, `int`, `str`
+/// or `bytes` respectively, containers are kept as serialized `bytes`.
+#[pyclass(module = "binn_rs", frozen, get_all)]
+#[derive(Debug)]
+pub struct UserValue {
+    subtype: u16,
+    storage: String,
+    value: PyObject,
+}
+
+#[pymethods]
+impl UserValue {
+    #[new]
+    #[pyo3(signature = (subtype, storage, value=None))]
+    fn new(py: Python<'_>, subtype: u16, storage: &str, value: Option<PyObject>) -> PyResult<Self> {
+        if subtype >= 4096 {
+            return Err(PyValueError::new_err("subtype must be less than 4096"));
+        }
+        if storage_from_name(storage).is_none() {
+            return Err(PyValueError::new_err(format!(
+                "unknown storage '{storage}'"
+            )));
+        }
+
+        Ok(Self {
+            subtype,
+            storage: storage.to_owned(),
+            value: value.unwrap_or_else(|| py.None()),
+        })
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let py = other.py();
+        let Ok(other) = other.downcast::<UserValue>() else {
+            return Ok(false);
+        };
+        let other = other.get();
+
+        Ok(self.subtype == other.subtype
+            && self.storage == other.storage
+            && self.value.bind(py).eq(&other.value)?)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "UserValue({}, '{}', {})",
+            self.subtype,
+            self.storage,
+            self.value.bind(py).repr()?
+        ))
+    }
+}
+
+/// Deserializes binn document into Python object
+#[pyfunction]
+fn loads<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    let value = Value::deserialize(data).map_err(binn_error)?;
+
+    to_python(py, value)
+}
+
+/// Serializes `list`, `tuple`, `dict` or container `UserValue` into binn document
+#[pyfunction]
+fn dumps<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    let data = serialize(obj)?;
+
+    Ok(PyBytes::new(obj.py(), &data))
+}
+
+/// Python module `binn_rs`
+#[pymodule]
+#[pyo3(name = "binn_rs")]
+pub fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("BinnError", m.py().get_type::<BinnError>())?;
+    m.add_class::<UserValue>()?;
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add_function(wrap_pyfunction!(dumps, m)?)?;
+
+    Ok(())
+}
+
+/// Error that might occur when Python object is serialized
+enum SerializeError {
+    Binn(Error),
+    Python(PyErr),
+}
+
+impl From<Error> for SerializeError {
+    fn from(value: Error) -> Self {
+        SerializeError::Binn(value)
+    }
+}
+
+impl From<PyErr> for SerializeError {
+    fn from(value: PyErr) -> Self {
+        SerializeError::Python(value)
+    }
+}
+
+/// Container that is being written
+enum Container<'a> {
+    List(List<'a>),
+    Map(Map<'a>),
+    Object(Object<'a>),
+}
+
+impl Container<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Container::List(list) => list.as_bytes(),
+            Container::Map(map) => map.as_bytes(),
+            Container::Object(obj) => obj.as_bytes(),
+        }
+    }
+}
+
+fn binn_error(err: Error) -> PyErr {
+    BinnError::new_err(format!("{err:?}"))
+}
+
+fn storage_from_name(name: &str) -> Option<Storage> {
+    let storage = match name {
+        "nobytes" => Storage::NoBytes,
+        "byte" => Storage::Byte,
+        "word" => Storage::Word,
+        "dword" => Storage::DWord,
+        "qword" => Storage::QWord,
+        "string" => Storage::String,
+        "blob" => Storage::Blob,
+        "container" => Storage::Container,
+        _ => return None,
+    };
+
+    Some(storage)
+}
+
+fn user_value<'py>(
+    py: Python<'py>,
+    subtype: SubType,
+    storage: &str,
+    value: impl IntoPyObject<'py>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let value = UserValue {
+        subtype: subtype.value(),
+        storage: storage.to_owned(),
+        value: value.into_py_any(py)?,
+    };
+
+    value.into_bound_py_any(py)
+}
+
+fn to_python<'py>(py: Python<'py>, value: Value<'_>) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Null => Ok(py.None().into_bound(py)),
+        Value::True => true.into_bound_py_any(py),
+        Value::False => false.into_bound_py_any(py),
+        Value::UInt8(v) => v.into_bound_py_any(py),
+        Value::Int8(v) => v.into_bound_py_any(py),
+        Value::UInt16(v) => v.into_bound_py_any(py),
+        Value::Int16(v) => v.into_bound_py_any(py),
+        Value::UInt32(v) => v.into_bound_py_any(py),
+        Value::Int32(v) => v.into_bound_py_any(py),
+        Value::Float(v) => v.into_bound_py_any(py),
+        Value::UInt64(v) => v.into_bound_py_any(py),
+        Value::Int64(v) => v.into_bound_py_any(py),
+        Value::Double(v) => v.into_bound_py_any(py),
+        Value::Text(v)
+        | Value::DateTime(v)
+        | Value::Date(v)
+        | Value::Time(v)
+        | Value::DecimalStr(v) => v.into_bound_py_any(py),
+        Value::Blob(v) => PyBytes::new(py, v).into_bound_py_any(py),
+        Value::UInt128(v) => v.into_bound_py_any(py),
+        Value::Int128(v) => v.into_bound_py_any(py),
+        Value::List(list) => {
+            let items = PyList::empty(py);
+            for item in list.iter() {
+                items.append(to_python(py, item)?)?;
+            }
+            items.into_bound_py_any(py)
+        }
+        Value::Map(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map.iter() {
+                dict.set_item(key, to_python(py, item)?)?;
+            }
+            dict.into_bound_py_any(py)
+        }
+        Value::Object(obj) => {
+            let dict = PyDict::new(py);
+            for (key, item) in obj.iter() {
+                dict.set_item(key, to_python(py, item)?)?;
+            }
+            dict.into_bound_py_any(py)
+        }
+        Value::Empty(sub) => user_value(py, sub, "nobytes", py.None()),
+        Value::Byte(sub, v) => user_value(py, sub, "byte", v),
+        Value::Word(sub, v) => user_value(py, sub, "word", v),
+        Value::DWord(sub, v) => user_value(py, sub, "dword", v),
+        Value::QWord(sub, v) => user_value(py, sub, "qword", v),
+        Value::UserText(sub, v) => user_value(py, sub, "string", v),
+        Value::UserBlob(sub, v) => user_value(py, sub, "blob", PyBytes::new(py, v)),
+        Value::UserContainer(container) => user_value(
+            py,
+            container.subtype(),
+            "container",
+            PyBytes::new(py, container.as_bytes()),
+        ),
+    }
+}
+
+/// Serializes Python container, buffer is grown until document fits
+fn serialize(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(value) = obj.downcast::<UserValue>() {
+        let value = value.get();
+        if value.storage == "container" {
+            let data: Vec<u8> = value.value.extract(obj.py())?;
+            return match Value::deserialize(&data).map_err(binn_error)? {
+                Value::UserContainer(c) if c.subtype().value() == value.subtype => Ok(data),
+                _ => Err(BinnError::new_err(
+                    "value is not a container of given subtype",
+                )),
+            };
+        }
+    }
+
+    let mut buf = vec![0; INITIAL_SIZE];
+    loop {
+        match write_container(obj, &mut buf) {
+            Ok(len) => {
+                buf.truncate(len);
+                return Ok(buf);
+            }
+            Err(SerializeError::Binn(Error::SmallBuffer(extra))) => {
+                let len = buf.len();
+                buf.resize(len + extra.max(len), 0);
+            }
+            Err(SerializeError::Binn(err)) => return Err(binn_error(err)),
+            Err(SerializeError::Python(err)) => return Err(err),
+        }
+    }
+}
+
+/// Writes Python container into given buffer and returns written size
+fn write_container(obj: &Bound<'_, PyAny>, buf: &mut [u8]) -> Result<usize, SerializeError> {
+    let container = if is_list(obj) {
+        let mut list = List::empty_mut(buf)?;
+        fill_list(obj, &mut list)?;
+        Container::List(list)
+    } else if let Ok(dict) = obj.downcast::<PyDict>() {
+        if is_map(dict) {
+            let mut map = Map::empty_mut(buf)?;
+            fill_map(dict, &mut map)?;
+            Container::Map(map)
+        } else {
+            let mut obj = Object::empty_mut(buf)?;
+            fill_object(dict, &mut obj)?;
+            Container::Object(obj)
+        }
+    } else {
+        let msg = format!(
+            "can't serialize '{}' as binn container",
+            obj.get_type().name()?
+        );
+        return Err(PyTypeError::new_err(msg).into());
+    };
+
+    Ok(container.as_bytes().len())
+}
+
+fn is_list(obj: &Bound<'_, PyAny>) -> bool {
+    obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>()
+}
+
+/// Dict is written as map when all keys are integers and as object otherwise
+fn is_map(dict: &Bound<'_, PyDict>) -> bool {
+    !dict.is_empty() && dict.keys().iter().all(|key| key.is_instance_of::<PyInt>())
+}
+
+fn fill_list(obj: &Bound<'_, PyAny>, list: &mut List<'_>) -> Result<(), SerializeError> {
+    for item in obj.try_iter()? {
+        write_item(&item?, list)?;
+    }
+
+    Ok(())
+}
+
+fn fill_map(dict: &Bound<'_, PyDict>, map: &mut Map<'_>) -> Result<(), SerializeError> {
+    for (key, item) in dict.iter() {
+        write_item(&item, &mut MapEntry(map, key.extract()?))?;
+    }
+
+    Ok(())
+}
+
+fn fill_object(dict: &Bound<'_, PyDict>, obj: &mut Object<'_>) -> Result<(), SerializeError> {
+    for (key, item) in dict.iter() {
+        let key = key
+            .downcast::<PyString>()
+            .map_err(|_| PyTypeError::new_err("dict keys must be either all int or all str"))?;
+        write_item(&item, &mut ObjectEntry(obj, key.to_str()?))?;
+    }
+
+    Ok(())
+}
+
+/// Place where converted Python value is added
+///
+/// Nested containers are built directly in buffer of parent container
+trait Sink {
+    fn add_value(&mut self, value: Value<'_>) -> Result<(), Error>;
+
+    fn add_list(
+        &mut self,
+        build: impl FnOnce(&mut List<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error>;
+
+    fn add_map(
+        &mut self,
+        build: impl FnOnce(&mut Map<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error>;
+
+    fn add_object(
+        &mut self,
+        build: impl FnOnce(&mut Object<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error>;
+}
+
+macro_rules! sink_impl {
+    ($name:ty, $this:ident => $target:expr $(, $key:expr)?) => {
+        impl Sink for $name {
+            fn add_value(&mut self, value: Value<'_>) -> Result<(), Error> {
+                let $this = self;
+                $target.add_value($($key,)? value).map(|_| ())
+            }
+
+            fn add_list(
+                &mut self,
+                build: impl FnOnce(&mut List<'_>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                let $this = self;
+                $target.add_list($($key,)? build)
+            }
+
+            fn add_map(
+                &mut self,
+                build: impl FnOnce(&mut Map<'_>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                let $this = self;
+                $target.add_map($($key,)? build)
+            }
+
+            fn add_object(
+                &mut self,
+                build: impl FnOnce(&mut Object<'_>) -> Result<(), Error>,
+            ) -> Result<(), Error> {
+                let $this = self;
+                $target.add_object($($key,)? build)
+            }
+        }
+    };
+}
+
+/// Map with key of item that is added next
+struct MapEntry<'m, 'a>(&'m mut Map<'a>, i32);
+
+/// Object with key of item that is added next
+struct ObjectEntry<'m, 'a, 'k>(&'m mut Object<'a>, &'k str);
+
+sink_impl!(List<'_>, list => list);
+sink_impl!(MapEntry<'_, '_>, entry => entry.0, entry.1);
+sink_impl!(ObjectEntry<'_, '_, '_>, entry => entry.0, entry.1);
+
+/// Python errors can't be returned from closures that build nested
+/// containers, so they are kept aside and replaced with binn error
+fn keep_python_error(
+    result: Result<(), SerializeError>,
+    kept: &mut Option<PyErr>,
+) -> Result<(), Error> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(SerializeError::Binn(err)) => Err(err),
+        Err(SerializeError::Python(err)) => {
+            *kept = Some(err);
+            Err(Error::TypeMismatch)
+        }
+    }
+}
+
+/// Returns error kept by [`keep_python_error`] instead of its replacement
+fn restore_python_error(
+    result: Result<(), Error>,
+    kept: Option<PyErr>,
+) -> Result<(), SerializeError> {
+    match kept {
+        Some(err) => Err(err.into()),
+        None => Ok(result?),
+    }
+}
+
+/// Converts Python object to binn value and adds it to `sink`
+fn write_item(item: &Bound<'_, PyAny>, sink: &mut impl Sink) -> Result<(), SerializeError> {
+    if item.is_none() {
+        sink.add_value(Value::Null)?;
+    } else if let Ok(v) = item.downcast::<PyBool>() {
+        sink.add_value(v.is_true().into())?;
+    } else if item.is_instance_of::<PyInt>() {
+        let value = match item.extract::<i128>() {
+            Ok(v) => Value::compact_int(v),
+            Err(_) => Value::UInt128(item.extract()?),
+        };
+        sink.add_value(value)?;
+    } else if let Ok(v) = item.downcast::<PyFloat>() {
+        sink.add_value(Value::Double(v.value()))?;
+    } else if let Ok(v) = item.downcast::<PyString>() {
+        sink.add_value(Value::Text(v.to_str()?))?;
+    } else if let Ok(v) = item.downcast::<PyBytes>() {
+        sink.add_value(Value::Blob(v.as_bytes()))?;
+    } else if let Ok(v) = item.downcast::<UserValue>() {
+        write_user_value(item, v.get(), sink)?;
+    } else if is_list(item) {
+        let mut kept = None;
+        let result = sink.add_list(|list| keep_python_error(fill_list(item, list), &mut kept));
+        restore_python_error(result, kept)?;
+    } else if let Ok(dict) = item.downcast::<PyDict>() {
+        let mut kept = None;
+        let result = if is_map(dict) {
+            sink.add_map(|map| keep_python_error(fill_map(dict, map), &mut kept))
+        } else {
+            sink.add_object(|obj| keep_python_error(fill_object(dict, obj), &mut kept))
+        };
+        restore_python_error(result, kept)?;
+    } else {
+        let msg = format!(
+            "can't serialize '{}' as binn value",
+            item.get_type().name()?
+        );
+        return Err(PyTypeError::new_err(msg).into());
+    }
+
+    Ok(())
+}
+
+fn write_user_value(
+    item: &Bound<'_, PyAny>,
+    value: &UserValue,
+    sink: &mut impl Sink,
+) -> Result<(), SerializeError> {
+    let sub = SubType::new(value.subtype);
+    let data = value.value.bind(item.py());
+
+    match storage_from_name(&value.storage) {
+        Some(Storage::NoBytes) => sink.add_value(Value::Empty(sub))?,
+        Some(Storage::Byte) => sink.add_value(Value::Byte(sub, data.extract()?))?,
+        Some(Storage::Word) => sink.add_value(Value::Word(sub, data.extract()?))?,
+        Some(Storage::DWord) => sink.add_value(Value::DWord(sub, data.extract()?))?,
+        Some(Storage::QWord) => sink.add_value(Value::QWord(sub, data.extract()?))?,
+        Some(Storage::String) => sink.add_value(Value::UserText(
+            sub,
+            data.downcast::<PyString>().map_err(PyErr::from)?.to_str()?,
+        ))?,
+        Some(Storage::Blob) => sink.add_value(Value::UserBlob(
+            sub,
+            data.downcast::<PyBytes>().map_err(PyErr::from)?.as_bytes(),
+        ))?,
+        _ => {
+            let data = serialize(item)?;
+            sink.add_value(Value::deserialize(&data)?)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use pyo3::ffi::c_str;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::wrap_pymodule;
+use std::ffi::CStr;
+
+/// Runs Python code with `binn_rs` module and `data` function that reads
+/// fixture of main crate (see `read_encoded` in its tests)
+fn run(code: &CStr) {
+    Python::with_gil(|py| {
+        let globals = PyDict::new(py);
+        let module = wrap_pymodule!(binn_rs_python::python_module)(py);
+        globals.set_item("binn_rs", module).unwrap();
+        py.run(
+            c_str!(
+                r#"
+def data(name):
+    encoded = bytearray()
+    with open(f'../tests/main/data/{name}.binn', 'rb') as f:
+        for line in f:
+            line = line.split(b'//')[0].strip()
+            while line:
+                if line.startswith(b'\\x'):
+                    encoded.append(int(line[2:4], 16))
+                    line = line[4:]
+                else:
+                    encoded.append(line[0])
+                    line = line[1:]
+    return bytes(encoded)
+"#
+            ),
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+
+        if let Err(err) = py.run(code, Some(&globals), None) {
+            err.display(py);
+            panic!("Python code failed");
+        }
+    });
+}
+
+#[test]
+fn loads() {
+    run(c_str!(
+        r#"
+nested = data('obj/nested')
+obj = binn_rs.loads(nested)
+assert obj['list'] == [{'map': {1: []}}, [], {}]
+assert obj['map'] == {7: {'obj': {}}, 8: [[[True]]]}
+assert obj['user'] == binn_rs.UserValue(20, 'container', nested[nested.index(b'\xF0\x14'):][:9])
+assert obj['big'] == [bytes(range(130)), {'text': 'b' * 100}]
+
+assert binn_rs.loads(data('obj/compressed_ints')) == {
+    'a': 5, 'b': -2, 'c': 300, 'd': -40000,
+    'e': 5000000000, 'f': -5000000000, 'g': 70000, 'h': -1,
+}
+assert binn_rs.loads(data('list/primitives'))[:3] == [None, True, False]
+"#
+    ));
+}
+
+#[test]
+fn dumps() {
+    run(c_str!(
+        r#"
+assert binn_rs.dumps({'key1': False, 'key2': 6262}) == bytes([
+    0xE2, 0x11, 0x02,
+    0x04, *b'key1', 0x02,
+    0x04, *b'key2', 0x40, 0x18, 0x76,
+])
+assert binn_rs.dumps({-1: 'x'}) == bytes([0xE1, 0x0B, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xA0, 0x01, *b'x', 0x00])
+assert binn_rs.dumps((1, -1)) == bytes([0xE0, 0x07, 0x02, 0x20, 0x01, 0x21, 0xFF])
+
+doc = {
+    'null': None, 'flag': True, 'int': -70000, 'big': 2**100, 'float': 1.5,
+    'text': 'пример', 'blob': b'\x00\x01',
+    'list': [[], {}, {1: [2]}], 'long': list(range(1000)),
+}
+assert binn_rs.loads(binn_rs.dumps(doc)) == doc
+# nested containers are written in place, buffer grows while they are built
+nested = {'a': [list(range(300)), {'b': 'x' * 500, 'c': {5: [b'\x00' * 700]}}]}
+assert binn_rs.loads(binn_rs.dumps(nested)) == nested
+# empty dict is always written as object, so nested fixture can't be used here
+for name in ['obj/compressed_ints', 'obj/unicode_keys', 'list/large']:
+    assert binn_rs.dumps(binn_rs.loads(data(name))) == data(name), name
+"#
+    ));
+}
+
+#[test]
+fn user_values() {
+    run(c_str!(
+        r#"
+UserValue = binn_rs.UserValue
+values = [
+    UserValue(5, 'nobytes'),
+    UserValue(5, 'byte', 200),
+    UserValue(6, 'word', 60000),
+    UserValue(7, 'dword', 1),
+    UserValue(8, 'qword', 2**64 - 1),
+    UserValue(9, 'string', 'text'),
+    UserValue(10, 'blob', b'\x01'),
+    UserValue(11, 'container', bytes([0xEB, 0x03, 0x00])),
+    UserValue(300, 'container', bytes([0xF1, 0x2C, 0x04, 0x00])),
+]
+assert binn_rs.loads(binn_rs.dumps(values)) == values
+assert repr(values[1]) == "UserValue(5, 'byte', 200)"
+assert values[1] != UserValue(5, 'byte', 201)
+"#
+    ));
+}
+
+#[test]
+fn errors() {
+    run(c_str!(
+        r#"
+def raises(error, f, *args):
+    try:
+        f(*args)
+    except error:
+        return
+    raise AssertionError(f'{error} was not raised')
+
+raises(binn_rs.BinnError, binn_rs.loads, b'\xE2\x03')
+raises(binn_rs.BinnError, binn_rs.dumps, {'k' * 256: 1})
+raises(TypeError, binn_rs.dumps, {1: 1, 'a': 2})
+raises(TypeError, binn_rs.dumps, [object()])
+raises(TypeError, binn_rs.dumps, 5)
+raises(TypeError, binn_rs.dumps, [[{1: 1, 'a': 2}]])
+raises(TypeError, binn_rs.dumps, {'a': {1: [object()]}})
+raises(OverflowError, binn_rs.dumps, {'a': [2**200]})
+raises(binn_rs.BinnError, binn_rs.dumps, [{'k' * 256: 1}])
+raises(OverflowError, binn_rs.dumps, [2**200])
+raises(ValueError, binn_rs.UserValue, 4096, 'byte', 1)
+raises(ValueError, binn_rs.UserValue, 1, 'unknown')
+assert issubclass(binn_rs.BinnError, ValueError)
+"#
+    ));
+}
@@ -0,0 +1,121 @@
+//! Canonical (deterministic) encoding of binn documents
+//!
+//! Canonical form of a document:
+//! * items of objects are sorted by their keys (compared as bytes)
+//! * items of maps are sorted by their keys (compared as numbers)
+//! * items of lists keep their order
+//! * integers of any width (including 128 bits) are stored in smallest type
+//!   that can hold them, unsigned types are used for non-negative numbers and
+//!   signed for negative
+//! * sizes use compact form whenever possible
+//!
+//! Items with equal keys keep their relative order. User-defined containers
+//! are copied as is since their keys are not known.
+use crate::error::Result;
+use crate::raw_container::{Key, KeyType, RawContainer};
+use crate::Value;
+use core::cmp::Ordering;
+
+/// Iterates over items of container in canonical order
+///
+/// Every step scans whole container, so full iteration takes O(n^2)
+/// but doesn't require any allocations
+pub struct SortedIter<'a> {
+    container: &'a RawContainer<'a>,
+    last: Option<(Key<'a>, usize)>,
+}
+
+impl<'a> SortedIter<'a> {
+    pub fn new(container: &'a RawContainer<'a>) -> Self {
+        Self {
+            container,
+            last: None,
+        }
+    }
+}
+
+impl<'a> Iterator for SortedIter<'a> {
+    type Item = (Key<'a>, Value<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next: Option<(usize, Key<'a>, Value<'a>)> = None;
+
+        for (pos, (key, value)) in self.container.iter().enumerate() {
+            if let Some((last_key, last_pos)) = self.last {
+                if cmp_items((key, pos), (last_key, last_pos)) != Ordering::Greater {
+                    continue;
+                }
+            }
+            let is_smaller = match &next {
+                Some((next_pos, next_key, _)) => {
+                    cmp_items((key, pos), (*next_key, *next_pos)) == Ordering::Less
+                }
+                None => true,
+            };
+            if is_smaller {
+                next = Some((pos, key, value));
+            }
+        }
+
+        let (pos, key, value) = next?;
+        self.last = Some((key, pos));
+
+        Some((key, value))
+    }
+}
+
+/// Compares items by their keys and then by their positions
+fn cmp_items(a: (Key<'_>, usize), b: (Key<'_>, usize)) -> Ordering {
+    let keys = match (a.0, b.0) {
+        (Key::Num(a), Key::Num(b)) => a.cmp(&b),
+        (Key::Str(a), Key::Str(b)) => a.as_bytes().cmp(b.as_bytes()),
+        _ => Ordering::Equal,
+    };
+
+    keys.then(a.1.cmp(&b.1))
+}
+
+/// Writes canonical form of all items from `src` into `dst`
+pub fn write_items(src: &RawContainer<'_>, dst: &mut RawContainer<'_>) -> Result<()> {
+    for (key, value) in SortedIter::new(src) {
+        match value {
+            Value::List(ref list) => write_container(key, &list.inner, dst)?,
+            Value::Map(ref map) => write_container(key, &map.inner, dst)?,
+            Value::Object(ref obj) => write_container(key, &obj.inner, dst)?,
+            value => {
+                let value = value.to_canonical_int().unwrap_or(value);
+                dst.add_value(key, value)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds empty container of the same type as `src` and writes canonical
+/// form of items from `src` there
+fn write_container(key: Key<'_>, src: &RawContainer<'_>, dst: &mut RawContainer<'_>) -> Result<()> {
+    let mut child = dst.add_empty_container(key, src.data_type(), src.key_type())?;
+
+    write_items(src, &mut child)
+}
+
+/// Checks whether items of two containers are equal when both are in canonical order
+///
+/// Values of items are compared with given function
+pub fn containers_eq(
+    a: &RawContainer<'_>,
+    b: &RawContainer<'_>,
+    value_eq: fn(&Value<'_>, &Value<'_>) -> bool,
+) -> bool {
+    if a.data_type() != b.data_type() || a.count() != b.count() {
+        return false;
+    }
+    if a.key_type() == KeyType::Unknown {
+        return a.as_bytes() == b.as_bytes();
+    }
+
+    SortedIter::new(a)
+        .zip(SortedIter::new(b))
+        .all(|((a_key, a_value), (b_key, b_value))| a_key == b_key && value_eq(&a_value, &b_value))
+}
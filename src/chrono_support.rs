@@ -0,0 +1,107 @@
+use crate::datetime::{self, Date, Time};
+use crate::error::{Error, Result};
+use crate::text_value::{TextKind, TextValue};
+use crate::Value;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+fn to_date(date: &NaiveDate) -> Date {
+    Date {
+        year: date.year(),
+        month: date.month() as u8,
+        day: date.day() as u8,
+    }
+}
+
+fn to_time(time: &NaiveTime) -> Time {
+    // chrono represents leap second as nanoseconds overflow
+    let (second, nanosecond) = if time.nanosecond() >= 1_000_000_000 {
+        (60, time.nanosecond() - 1_000_000_000)
+    } else {
+        (time.second() as u8, time.nanosecond())
+    };
+
+    Time {
+        hour: time.hour() as u8,
+        minute: time.minute() as u8,
+        second,
+        nanosecond,
+    }
+}
+
+fn from_date(date: Date) -> Result<NaiveDate> {
+    NaiveDate::from_ymd_opt(date.year, date.month as u32, date.day as u32)
+        .ok_or(Error::InvalidFormat)
+}
+
+fn from_time(time: Time) -> Result<NaiveTime> {
+    let (second, nanosecond) = if time.second == 60 {
+        (59, time.nanosecond + 1_000_000_000)
+    } else {
+        (time.second as u32, time.nanosecond)
+    };
+
+    NaiveTime::from_hms_nano_opt(time.hour as u32, time.minute as u32, second, nanosecond)
+        .ok_or(Error::InvalidFormat)
+}
+
+fn from_date_time((date, time): (Date, Time)) -> Result<NaiveDateTime> {
+    Ok(NaiveDateTime::new(from_date(date)?, from_time(time)?))
+}
+
+impl From<NaiveDateTime> for TextValue {
+    fn from(value: NaiveDateTime) -> Self {
+        TextValue::format(TextKind::DateTime, |text| {
+            datetime::write_date_time(&to_date(&value.date()), &to_time(&value.time()), text)
+        })
+    }
+}
+
+impl From<NaiveDate> for TextValue {
+    fn from(value: NaiveDate) -> Self {
+        TextValue::format(TextKind::Date, |text| to_date(&value).write(text))
+    }
+}
+
+impl From<NaiveTime> for TextValue {
+    fn from(value: NaiveTime) -> Self {
+        TextValue::format(TextKind::Time, |text| to_time(&value).write(text))
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for NaiveDateTime {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::DateTime(text) = value {
+            datetime::parse_date_time(text)
+                .and_then(from_date_time)
+                .map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for NaiveDate {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::Date(text) = value {
+            Date::parse(text).and_then(from_date).map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for NaiveTime {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::Time(text) = value {
+            Time::parse(text).and_then(from_time).map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Builders of binn documents that can be used in const context
+use crate::data_type::{self, Type};
+use crate::size::Size;
+use crate::storage::Storage;
+use crate::Value;
+
+/// Items of container without header, header is written only when
+/// final size is known
+#[derive(Clone, Debug)]
+struct ConstRaw<const N: usize> {
+    data_type: Type,
+    items: [u8; N],
+    len: usize,
+    count: usize,
+}
+
+impl<const N: usize> ConstRaw<N> {
+    const fn new(data_type: Type) -> Self {
+        Self {
+            data_type,
+            items: [0; N],
+            len: 0,
+            count: 0,
+        }
+    }
+
+    /// Returns buffer where next item should be written
+    const fn insert_position(&mut self, size: usize) -> &mut [u8] {
+        if self.len + size > N {
+            panic!("binn document doesn't fit into capacity");
+        }
+        self.items.split_at_mut(self.len).1
+    }
+
+    const fn key_num(mut self, key: i32) -> Self {
+        let bytes = key.to_be_bytes();
+        let buf = self.insert_position(4);
+        buf[0] = bytes[0];
+        buf[1] = bytes[1];
+        buf[2] = bytes[2];
+        buf[3] = bytes[3];
+        self.len += 4;
+        self
+    }
+
+    const fn key_str(mut self, key: &str) -> Self {
+        let key = key.as_bytes();
+        if key.len() > 255 {
+            panic!("key is longer than 255 bytes");
+        }
+
+        let buf = self.insert_position(key.len() + 1);
+        buf[0] = key.len() as u8;
+        copy(buf.split_at_mut(1).1, key);
+        self.len += key.len() + 1;
+        self
+    }
+
+    /// Writes \[type\] \[size\] \[data\] of given primitive value
+    ///
+    /// # Panics
+    ///
+    /// Panics if value is container
+    const fn value(mut self, value: &Value<'_>) -> Self {
+        let value_type = value.get_type();
+        if matches!(value_type.storage, Storage::Container) {
+            panic!("containers should be added with add_list, add_map or add_object");
+        }
+
+        let size_entry = value.size_entry();
+        let size = match (value.data_size(), size_entry) {
+            (Some(data_size), Some(size)) => size.size() + data_size,
+            _ => match value_type.storage.fixed_size() {
+                Some(size) => size,
+                None => unreachable!(),
+            },
+        };
+        let total_size = value_type.size() + size;
+
+        let buf = value_type.write(self.insert_position(total_size));
+        let buf = match size_entry {
+            Some(size) => match size.write(buf) {
+                Ok(buf) => buf,
+                Err(_) => unreachable!(),
+            },
+            None => buf,
+        };
+
+        match value {
+            Value::Null | Value::True | Value::False | Value::Empty(_) => {}
+
+            Value::UInt8(val) | Value::Byte(_, val) => buf[0] = *val,
+            Value::Int8(val) => buf[0] = *val as u8,
+            Value::UInt16(val) | Value::Word(_, val) => copy(buf, &val.to_be_bytes()),
+            Value::Int16(val) => copy(buf, &val.to_be_bytes()),
+            Value::UInt32(val) | Value::DWord(_, val) => copy(buf, &val.to_be_bytes()),
+            Value::Int32(val) => copy(buf, &val.to_be_bytes()),
+            Value::Float(val) => copy(buf, &val.to_bits().to_be_bytes()),
+            Value::UInt64(val) | Value::QWord(_, val) => copy(buf, &val.to_be_bytes()),
+            Value::Int64(val) => copy(buf, &val.to_be_bytes()),
+            Value::Double(val) => copy(buf, &val.to_bits().to_be_bytes()),
+            Value::UInt128(val) => copy(buf, &val.to_be_bytes()),
+            Value::Int128(val) => copy(buf, &val.to_be_bytes()),
+
+            Value::Text(val)
+            | Value::DateTime(val)
+            | Value::Date(val)
+            | Value::Time(val)
+            | Value::DecimalStr(val)
+            | Value::UserText(_, val) => {
+                copy(buf, val.as_bytes());
+                // null terminator
+                buf[val.len()] = 0;
+            }
+
+            Value::Blob(val) | Value::UserBlob(_, val) => copy(buf, val),
+
+            _ => unreachable!(),
+        }
+
+        self.len += total_size;
+        self.count += 1;
+        self
+    }
+
+    const fn container<const M: usize>(mut self, container: &ConstRaw<M>) -> Self {
+        let size = container.encoded_len();
+        container.write(self.insert_position(size));
+        self.len += size;
+        self.count += 1;
+        self
+    }
+
+    /// Returns \[size\] and \[count\] of this container when it is encoded
+    const fn header(&self) -> (Size, Size) {
+        let count = match Size::new(self.count) {
+            Ok(count) => count,
+            Err(_) => panic!("too many items"),
+        };
+
+        // size includes itself so try compact form first
+        let len = self.data_type.size() + 1 + count.size() + self.len;
+        let len = if Size::is_compactable(len) {
+            Size::Compact(len as u8)
+        } else {
+            Size::Full((len + 3) as u32)
+        };
+
+        (len, count)
+    }
+
+    const fn encoded_len(&self) -> usize {
+        self.header().0.value()
+    }
+
+    /// Writes whole container into given buffer
+    const fn write(&self, buf: &mut [u8]) {
+        let (len, count) = self.header();
+        let buf = self.data_type.write(buf);
+        let buf = match len.write(buf) {
+            Ok(buf) => buf,
+            Err(_) => panic!("buffer is too small"),
+        };
+        let buf = match count.write(buf) {
+            Ok(buf) => buf,
+            Err(_) => panic!("buffer is too small"),
+        };
+
+        copy(buf, self.items.split_at(self.len).0);
+    }
+
+    const fn to_array<const M: usize>(&self) -> [u8; M] {
+        if M != self.encoded_len() {
+            panic!("array size must be equal to encoded length");
+        }
+
+        let mut bytes = [0; M];
+        self.write(&mut bytes);
+        bytes
+    }
+}
+
+/// Copies all bytes from source to the beginning of destination
+const fn copy(dst: &mut [u8], src: &[u8]) {
+    let mut i = 0;
+    while i < src.len() {
+        dst[i] = src[i];
+        i += 1;
+    }
+}
+
+macro_rules! const_container_impl {
+    ($name:ident, $data_type:expr, $what:literal, ($($key:ident: $key_type:ty => $key_fn:ident)?)) => {
+        impl<const N: usize> $name<N> {
+            /// Creates new empty
+            #[doc = $what]
+            pub const fn new() -> Self {
+                Self {
+                    raw: ConstRaw::new($data_type),
+                }
+            }
+
+            /// Adds primitive value
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile in const context) if value is
+            /// container or capacity is exceeded
+            pub const fn add(self, $($key: $key_type,)? value: Value<'_>) -> Self {
+                let raw = self.raw;
+                $(let raw = raw.$key_fn($key);)?
+                Self {
+                    raw: raw.value(&value),
+                }
+            }
+
+            /// Adds nested list
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile in const context) if capacity is exceeded
+            pub const fn add_list<const M: usize>(self, $($key: $key_type,)? list: &ConstList<M>) -> Self {
+                let raw = self.raw;
+                $(let raw = raw.$key_fn($key);)?
+                Self {
+                    raw: raw.container(&list.raw),
+                }
+            }
+
+            /// Adds nested map
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile in const context) if capacity is exceeded
+            pub const fn add_map<const M: usize>(self, $($key: $key_type,)? map: &ConstMap<M>) -> Self {
+                let raw = self.raw;
+                $(let raw = raw.$key_fn($key);)?
+                Self {
+                    raw: raw.container(&map.raw),
+                }
+            }
+
+            /// Adds nested object
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile in const context) if capacity is exceeded
+            pub const fn add_object<const M: usize>(self, $($key: $key_type,)? obj: &ConstObject<M>) -> Self {
+                let raw = self.raw;
+                $(let raw = raw.$key_fn($key);)?
+                Self {
+                    raw: raw.container(&obj.raw),
+                }
+            }
+
+            /// Returns how many bytes encoded document takes
+            pub const fn len(&self) -> usize {
+                self.raw.encoded_len()
+            }
+
+            /// Returns number of elements
+            pub const fn count(&self) -> usize {
+                self.raw.count
+            }
+
+            /// Returns whether there are no elements
+            pub const fn is_empty(&self) -> bool {
+                self.raw.count == 0
+            }
+
+            /// Returns encoded document
+            ///
+            /// # Panics
+            ///
+            /// Panics (or fails to compile in const context) if `M` is
+            /// not equal to [`len`](Self::len)
+            pub const fn to_array<const M: usize>(&self) -> [u8; M] {
+                self.raw.to_array()
+            }
+        }
+
+        impl<const N: usize> Default for $name<N> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+/// List that can be built in const context
+///
+/// Items are stored in buffer of `N` bytes, encoded document is
+/// received with [`to_array`](Self::to_array):
+/// ```
+/// use binn_rs::{ConstList, ConstObject, List, Value};
+///
+/// const CAPS: ConstList<16> = ConstList::new()
+///     .add(Value::Text("read"))
+///     .add(Value::Text("write"));
+///
+/// const HANDSHAKE: ConstObject<64> = ConstObject::new()
+///     .add("version", Value::UInt8(2))
+///     .add_list("caps", &CAPS);
+///
+/// static HANDSHAKE_BYTES: [u8; HANDSHAKE.len()] = HANDSHAKE.to_array();
+///
+/// let value = Value::deserialize(&HANDSHAKE_BYTES).unwrap();
+/// let obj: binn_rs::Object = value.try_into().unwrap();
+/// assert_eq!(obj.get("version").unwrap(), Value::UInt8(2));
+///
+/// let caps: List = obj.get("caps").unwrap().try_into().unwrap();
+/// assert_eq!(caps.get(1).unwrap(), Value::Text("write"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConstList<const N: usize> {
+    raw: ConstRaw<N>,
+}
+
+/// Map that can be built in const context
+///
+/// See [`ConstList`] for example
+#[derive(Clone, Debug)]
+pub struct ConstMap<const N: usize> {
+    raw: ConstRaw<N>,
+}
+
+/// Object that can be built in const context
+///
+/// See [`ConstList`] for example
+#[derive(Clone, Debug)]
+pub struct ConstObject<const N: usize> {
+    raw: ConstRaw<N>,
+}
+
+const_container_impl!(ConstList, data_type::LIST, "list", ());
+const_container_impl!(ConstMap, data_type::MAP, "map", (key: i32 => key_num));
+const_container_impl!(ConstObject, data_type::OBJECT, "object", (key: &str => key_str));
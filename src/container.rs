@@ -1,7 +1,13 @@
+use crate::canonical;
+use crate::data_type::{self, Type};
 use crate::error::Result;
-use crate::raw_container::{Key, KeyType, RawContainer};
+use crate::iter::{ListIter, MapIter, ObjectIter};
+use crate::raw_container::{Key, KeyType, Position, RawContainer};
+use crate::size::Size;
+use crate::storage::Storage;
 use crate::Allocation;
-use crate::{Error, Value};
+use crate::{Error, RawValue, SubType, Value};
+use core::ops::Index;
 
 const EMPTY_LIST: &[u8] = &[0xE0, 0x03, 0x00];
 const EMPTY_MAP: &[u8] = &[0xE1, 0x03, 0x00];
@@ -28,6 +34,77 @@ pub struct Object<'a> {
     pub(crate) inner: RawContainer<'a>,
 }
 
+/// Container of user-defined type
+///
+/// Binn doesn't define how items of such containers are stored, so it
+/// must be interpreted as list, map or object before items can be accessed:
+/// ```
+/// use binn_rs::{List, SubType, UserContainer, Value};
+///
+/// const POINTS: SubType = SubType::new(5);
+///
+/// let mut buf = [0; 32];
+/// let mut list = List::empty_mut_with_subtype(POINTS, buf.as_mut_slice()).unwrap();
+/// list.add_value(5u8).unwrap();
+///
+/// let container: UserContainer = Value::deserialize(list.as_bytes())
+///     .unwrap()
+///     .try_into()
+///     .unwrap();
+/// assert_eq!(container.subtype(), POINTS);
+///
+/// let list = container.into_list().unwrap();
+/// assert_eq!(list.get(0).unwrap(), Value::UInt8(5));
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct UserContainer<'a> {
+    pub(crate) inner: RawContainer<'a>,
+}
+
+impl<'a> UserContainer<'a> {
+    /// Returns slice of bytes representing this container
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+
+    /// Returns number of elements in this container
+    pub fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    /// Interprets this container as list
+    ///
+    /// Returns [`Error::Malformed`] if items can't be parsed as list items
+    pub fn into_list(self) -> Result<List<'a>> {
+        Ok(List {
+            inner: self.inner.with_key_type(KeyType::Empty)?,
+        })
+    }
+
+    /// Interprets this container as map
+    ///
+    /// Returns [`Error::Malformed`] if items can't be parsed as map items
+    pub fn into_map(self) -> Result<Map<'a>> {
+        Ok(Map {
+            inner: self.inner.with_key_type(KeyType::Num)?,
+        })
+    }
+
+    /// Interprets this container as object
+    ///
+    /// Returns [`Error::Malformed`] if items can't be parsed as object items
+    pub fn into_object(self) -> Result<Object<'a>> {
+        Ok(Object {
+            inner: self.inner.with_key_type(KeyType::Str)?,
+        })
+    }
+
+    /// Returns sub type of this container
+    pub fn subtype(&self) -> SubType {
+        self.inner.data_type().subtype
+    }
+}
+
 impl<'a> List<'a> {
     /// Adds new value to this list
     pub fn add_value<'c, 'p: 'c, 'd>(
@@ -37,6 +114,34 @@ impl<'a> List<'a> {
         self.inner.add_value(Key::Empty, value.into())
     }
 
+    /// Adds already serialized value to this list
+    ///
+    /// See [`Object::add_raw`] for details
+    pub fn add_raw(&mut self, raw: &[u8]) -> Result<()> {
+        add_raw(&mut self.inner, Key::Empty, raw)
+    }
+
+    /// Adds new list and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_list(&mut self, build: impl FnOnce(&mut List<'_>) -> Result<()>) -> Result<()> {
+        add_list(&mut self.inner, Key::Empty, build)
+    }
+
+    /// Adds new map and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_map(&mut self, build: impl FnOnce(&mut Map<'_>) -> Result<()>) -> Result<()> {
+        add_map(&mut self.inner, Key::Empty, build)
+    }
+
+    /// Adds new object and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_object(&mut self, build: impl FnOnce(&mut Object<'_>) -> Result<()>) -> Result<()> {
+        add_object(&mut self.inner, Key::Empty, build)
+    }
+
     /// Returns slice of bytes representing current document.
     ///
     /// It's guaranteed to be subslice of initial buffer
@@ -47,36 +152,243 @@ impl<'a> List<'a> {
         self.inner.as_bytes()
     }
 
+    /// Enables or disables compaction of integers added to this list
+    ///
+    /// When enabled, integers (up to 64 bits) are stored in smallest type that
+    /// can hold them (see [`Value::compact_int`]), so `5i64` takes 2 bytes instead of 9.
+    /// Integers can be read back as any type that fits them with [`get_as`](Self::get_as).
+    /// Containers added to this list inherit this setting.
+    ///
+    /// Disabled by default
+    pub fn set_compact_ints(&mut self, compact: bool) {
+        self.inner.set_compact_ints(compact);
+    }
+
+    /// Returns whether integers added to this list are compacted
+    pub fn compact_ints(&self) -> bool {
+        self.inner.compact_ints()
+    }
+
+    /// Writes canonical form of this list into given allocation
+    ///
+    /// See [`Object::to_canonical`] for details about canonical form
+    pub fn to_canonical<'b>(&self, allocation: impl Into<Allocation<'b>>) -> Result<List<'b>> {
+        let mut canonical = List::empty_mut_with_subtype(self.subtype(), allocation)?;
+        canonical::write_items(&self.inner, &mut canonical.inner)?;
+
+        Ok(canonical)
+    }
+
     /// Returns number of elements in this list
     pub fn count(&self) -> usize {
         self.inner.count()
     }
 
+    /// Returns sub type of this list
+    ///
+    /// It's different from predefined one only for user-defined containers
+    pub fn subtype(&self) -> SubType {
+        self.inner.data_type().subtype
+    }
+
     /// Returns new empty list
     ///
     /// List is read only so no new elements can be added to it
-    pub fn empty() -> List<'static> {
-        // EMPTY_LIST can't be malformed
-        List {
-            inner: RawContainer::from_bytes(EMPTY_LIST, KeyType::Empty).unwrap(),
-        }
+    pub const fn empty() -> List<'static> {
+        List::from_static(EMPTY_LIST)
     }
 
     /// Creates a new list that uses given allocation for storage
     pub fn empty_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
         Ok(Self {
-            inner: empty_mut(allocation.into(), EMPTY_LIST, KeyType::Empty)?,
+            inner: empty_mut(allocation.into(), data_type::LIST, KeyType::Empty)?,
+        })
+    }
+
+    /// Creates a new list of user-defined type that uses given allocation for storage
+    ///
+    /// Sub types 0..=2 are reserved for standard containers, so
+    /// [`Error::TypeMismatch`] is returned for them, except for 0 which creates
+    /// standard list
+    pub fn empty_mut_with_subtype(
+        subtype: SubType,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: empty_mut(
+                allocation.into(),
+                container_type(subtype, data_type::LIST)?,
+                KeyType::Empty,
+            )?,
         })
     }
 
+    /// Opens list that is already serialized in given allocation for modification
+    ///
+    /// Allocation must start with serialized list (standard or of user-defined type),
+    /// bytes after it are used for new items. Returns [`Error::TypeMismatch`] if
+    /// allocation starts with other container and [`Error::Malformed`] if list
+    /// can't be parsed.
+    ///
+    /// ```
+    /// use binn_rs::{List, Value};
+    ///
+    /// let mut buf = [0; 16];
+    /// let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    /// list.add_value(1u8).unwrap();
+    ///
+    /// // same buffer is opened later, e.g. after it was loaded from flash
+    /// let mut list = List::open_mut(buf.as_mut_slice()).unwrap();
+    /// list.add_value(2u8).unwrap();
+    ///
+    /// assert_eq!(list.count(), 2);
+    /// assert_eq!(list.get(1), Some(Value::UInt8(2)));
+    /// ```
+    pub fn open_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
+        Ok(Self {
+            inner: open_mut(allocation.into(), data_type::LIST, KeyType::Empty)?,
+        })
+    }
+
+    /// Reads list serialized at start of given bytes
+    ///
+    /// Unlike `Value::deserialize(bytes)?.try_into()` it returns [`Error::TypeMismatch`]
+    /// if bytes start with any other value than list (standard or of user-defined type).
+    /// Bytes after serialized list are ignored.
+    ///
+    /// ```
+    /// use binn_rs::{Error, List, Object, Value};
+    ///
+    /// let bytes = [0xE0, 0x05, 0x02, 0x01, 0x00];
+    /// let list = List::from_bytes(&bytes).unwrap();
+    /// assert_eq!(list.get(0), Some(Value::True));
+    ///
+    /// assert!(matches!(Object::from_bytes(&bytes), Err(Error::TypeMismatch)));
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            inner: from_bytes(bytes, data_type::LIST, KeyType::Empty)?,
+        })
+    }
+
+    /// Reads list serialized at start of given static bytes in const context
+    ///
+    /// Works like [`from_bytes`](Self::from_bytes), but when used in constant
+    /// bytes are checked at compile time, so read-only handle costs nothing
+    /// at runtime. It can be used with bytes from [`ConstList`](crate::ConstList).
+    ///
+    /// ```
+    /// use binn_rs::{List, Value};
+    ///
+    /// const BYTES: &[u8] = &[0xE0, 0x05, 0x02, 0x01, 0x00];
+    /// const LIST: List<'static> = List::from_static(BYTES);
+    ///
+    /// assert_eq!(LIST.get(0), Some(Value::True));
+    /// ```
+    ///
+    /// Malformed bytes are rejected at compile time:
+    /// ```compile_fail
+    /// use binn_rs::List;
+    ///
+    /// // [size] is larger than the list
+    /// static LIST: List<'static> = List::from_static(&[0xE0, 0x06, 0x00]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in const context) if bytes don't start
+    /// with valid list (standard or of user-defined type)
+    pub const fn from_static(bytes: &'static [u8]) -> List<'static> {
+        List {
+            inner: from_static(bytes, data_type::LIST, KeyType::Empty),
+        }
+    }
+
     /// Get value at position
     pub fn get(&self, pos: usize) -> Option<Value<'_>> {
         self.inner.get_at(pos)
     }
 
+    /// Get serialized value at position without decoding it
+    pub fn get_raw(&self, pos: usize) -> Option<&RawValue> {
+        self.inner.get_raw_at(pos)
+    }
+
+    /// Get value at position converted to given type
+    ///
+    /// Returns `None` if there is no such value or it can't be converted
+    pub fn get_as<'b, T: TryFrom<Value<'b>>>(&'b self, pos: usize) -> Option<T> {
+        self.get(pos).and_then(|v| v.try_into().ok())
+    }
+
     /// Iterate over elements of this list
-    pub fn iter(&self) -> impl Iterator<Item = Value<'_>> {
-        self.inner.iter().map(|(_, v)| v)
+    pub fn iter(&self) -> ListIter<'_> {
+        ListIter::new(self.inner.iter())
+    }
+
+    /// Iterate over elements of this list starting at given position
+    ///
+    /// Position is obtained from [`ListIter::current_position`] of iterator over
+    /// this list, so iteration can be resumed without going through
+    /// preceding elements again. Elements after position are checked, so
+    /// `None` is returned if position doesn't point to element of this list
+    /// (e.g. it comes from other list or elements were removed since)
+    ///
+    /// ```
+    /// use binn_rs::{List, Value};
+    ///
+    /// let mut buf = [0; 32];
+    /// let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    /// for i in 0..5u8 {
+    ///     list.add_value(i).unwrap();
+    /// }
+    ///
+    /// let mut iter = list.iter();
+    /// iter.nth(2);
+    /// let position = iter.current_position();
+    ///
+    /// let rest = list.iter_from(position).unwrap();
+    /// assert_eq!(rest.len(), 2);
+    /// assert!(rest.rev().eq([Value::UInt8(4), Value::UInt8(3)]));
+    /// ```
+    pub fn iter_from(&self, position: Position) -> Option<ListIter<'_>> {
+        self.inner.iter_at(position).map(ListIter::new)
+    }
+
+    /// Iterate over elements of this list with their serialized form
+    ///
+    /// Each item contains offset of serialized value from the start of
+    /// [`as_bytes`](Self::as_bytes), serialized value and decoded value
+    pub fn iter_raw(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, &[u8], Value<'_>)> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(_, offset, raw)| (offset, raw, Value::deserialize_valid(raw)))
+    }
+
+    /// Iterate over elements of this list without decoding them
+    ///
+    /// Values are decoded only when [`RawValue::decode`] is called, so
+    /// iteration is cheap when only some of elements are needed:
+    ///
+    /// ```
+    /// use binn_rs::{List, Storage, Value};
+    ///
+    /// let mut buf = [0; 32];
+    /// let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    /// list.add_value("text").unwrap();
+    /// list.add_value(5u8).unwrap();
+    ///
+    /// let numbers: Vec<_> = list
+    ///     .iter_lazy()
+    ///     .filter(|raw| raw.peek_type().0 == Storage::Byte)
+    ///     .map(|raw| raw.decode())
+    ///     .collect();
+    /// assert_eq!(numbers, [Value::UInt8(5)]);
+    /// ```
+    pub fn iter_lazy(&self) -> impl DoubleEndedIterator<Item = &RawValue> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(_, _, raw)| RawValue::new_unchecked(raw))
     }
 }
 
@@ -90,6 +402,46 @@ impl<'a> Map<'a> {
         self.inner.add_value(Key::Num(key), value.into())
     }
 
+    /// Adds already serialized value with given key to this map
+    ///
+    /// See [`Object::add_raw`] for details
+    pub fn add_raw(&mut self, key: i32, raw: &[u8]) -> Result<()> {
+        add_raw(&mut self.inner, Key::Num(key), raw)
+    }
+
+    /// Adds new list with given key and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_list(
+        &mut self,
+        key: i32,
+        build: impl FnOnce(&mut List<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_list(&mut self.inner, Key::Num(key), build)
+    }
+
+    /// Adds new map with given key and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_map(
+        &mut self,
+        key: i32,
+        build: impl FnOnce(&mut Map<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_map(&mut self.inner, Key::Num(key), build)
+    }
+
+    /// Adds new object with given key and fills it with given closure
+    ///
+    /// See [`Object::add_object`] for details
+    pub fn add_object(
+        &mut self,
+        key: i32,
+        build: impl FnOnce(&mut Object<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_object(&mut self.inner, Key::Num(key), build)
+    }
+
     /// Returns slice of bytes representing current document.
     ///
     /// It's guaranteed to be subslice of initial buffer
@@ -100,36 +452,174 @@ impl<'a> Map<'a> {
         self.inner.as_bytes()
     }
 
+    /// Enables or disables compaction of integers added to this map
+    ///
+    /// See [`List::set_compact_ints`]
+    pub fn set_compact_ints(&mut self, compact: bool) {
+        self.inner.set_compact_ints(compact);
+    }
+
+    /// Returns whether integers added to this map are compacted
+    pub fn compact_ints(&self) -> bool {
+        self.inner.compact_ints()
+    }
+
+    /// Writes canonical form of this map into given allocation
+    ///
+    /// See [`Object::to_canonical`] for details about canonical form
+    pub fn to_canonical<'b>(&self, allocation: impl Into<Allocation<'b>>) -> Result<Map<'b>> {
+        let mut canonical = Map::empty_mut_with_subtype(self.subtype(), allocation)?;
+        canonical::write_items(&self.inner, &mut canonical.inner)?;
+
+        Ok(canonical)
+    }
+
     /// Returns number of elements in this map
     pub fn count(&self) -> usize {
         self.inner.count()
     }
 
+    /// Returns sub type of this map
+    ///
+    /// It's different from predefined one only for user-defined containers
+    pub fn subtype(&self) -> SubType {
+        self.inner.data_type().subtype
+    }
+
     /// Returns new empty object
     ///
     /// Object is read only so no new elements can be added to it
-    pub fn empty() -> Map<'static> {
-        // EMPTY_MAP can't be malformed
-        Map {
-            inner: RawContainer::from_bytes(EMPTY_MAP, KeyType::Num).unwrap(),
-        }
+    pub const fn empty() -> Map<'static> {
+        Map::from_static(EMPTY_MAP)
     }
 
     /// Creates a new object that uses given allocation for storage
     pub fn empty_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
         Ok(Self {
-            inner: empty_mut(allocation.into(), EMPTY_MAP, KeyType::Num)?,
+            inner: empty_mut(allocation.into(), data_type::MAP, KeyType::Num)?,
+        })
+    }
+
+    /// Creates a new map of user-defined type that uses given allocation for storage
+    ///
+    /// Sub types 0..=2 are reserved for standard containers, so
+    /// [`Error::TypeMismatch`] is returned for them, except for 1 which creates
+    /// standard map
+    pub fn empty_mut_with_subtype(
+        subtype: SubType,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: empty_mut(
+                allocation.into(),
+                container_type(subtype, data_type::MAP)?,
+                KeyType::Num,
+            )?,
         })
     }
 
+    /// Opens map that is already serialized in given allocation for modification
+    ///
+    /// See [`List::open_mut`] for details
+    pub fn open_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
+        Ok(Self {
+            inner: open_mut(allocation.into(), data_type::MAP, KeyType::Num)?,
+        })
+    }
+
+    /// Reads map serialized at start of given bytes
+    ///
+    /// See [`List::from_bytes`] for details
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            inner: from_bytes(bytes, data_type::MAP, KeyType::Num)?,
+        })
+    }
+
+    /// Reads map serialized at start of given static bytes in const context
+    ///
+    /// Works like [`from_bytes`](Self::from_bytes), but when used in constant
+    /// bytes are checked at compile time, so read-only handle costs nothing
+    /// at runtime. It can be used with bytes from [`ConstMap`](crate::ConstMap).
+    ///
+    /// ```
+    /// use binn_rs::{Map, Value};
+    ///
+    /// const BYTES: &[u8] = &[0xE1, 0x08, 0x01, 0x00, 0x00, 0x00, 0x07, 0x01];
+    /// const MAP: Map<'static> = Map::from_static(BYTES);
+    ///
+    /// assert_eq!(MAP.get(7), Some(Value::True));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in const context) if bytes don't start
+    /// with valid map (standard or of user-defined type)
+    pub const fn from_static(bytes: &'static [u8]) -> Map<'static> {
+        Map {
+            inner: from_static(bytes, data_type::MAP, KeyType::Num),
+        }
+    }
+
     /// Get value with specific key
     pub fn get(&self, key: i32) -> Option<Value<'_>> {
         self.inner.get(Key::Num(key))
     }
 
+    /// Get serialized value with specific key without decoding it
+    pub fn get_raw(&self, key: i32) -> Option<&RawValue> {
+        self.inner.get_raw(Key::Num(key))
+    }
+
+    /// Get value with specific key converted to given type
+    ///
+    /// Returns `None` if there is no such value or it can't be converted
+    pub fn get_as<'b, T: TryFrom<Value<'b>>>(&'b self, key: i32) -> Option<T> {
+        self.get(key).and_then(|v| v.try_into().ok())
+    }
+
     /// Iterate over elements of this map
-    pub fn iter(&self) -> impl Iterator<Item = (i32, Value<'_>)> {
-        self.inner.iter().map(|(k, v)| (k.to_num().unwrap(), v))
+    pub fn iter(&self) -> MapIter<'_> {
+        MapIter::new(self.inner.iter())
+    }
+
+    /// Iterate over elements of this map starting at given position
+    ///
+    /// See [`List::iter_from`] for details
+    pub fn iter_from(&self, position: Position) -> Option<MapIter<'_>> {
+        self.inner.iter_at(position).map(MapIter::new)
+    }
+
+    /// Iterate over elements of this map with their serialized form
+    ///
+    /// See [`List::iter_raw`] for details
+    pub fn iter_raw(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (i32, usize, &[u8], Value<'_>)> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(key, offset, raw)| {
+            (
+                key.to_num().unwrap(),
+                offset,
+                raw,
+                Value::deserialize_valid(raw),
+            )
+        })
+    }
+
+    /// Iterate over elements of this map without decoding them
+    ///
+    /// See [`List::iter_lazy`] for details
+    pub fn iter_lazy(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (i32, &RawValue)> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(key, _, raw)| (key.to_num().unwrap(), RawValue::new_unchecked(raw)))
+    }
+
+    /// Iterate over keys of this map without decoding values
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = i32> + ExactSizeIterator + '_ {
+        self.inner.iter().keys().map(|key| key.to_num().unwrap())
     }
 }
 
@@ -143,6 +633,87 @@ impl<'a> Object<'a> {
         self.inner.add_value(Key::Str(key), value.into())
     }
 
+    /// Adds already serialized value with given name to this object
+    ///
+    /// Bytes must contain exactly one valid value, otherwise error is
+    /// returned. Value is copied as is without re-encoding, so items can be
+    /// moved between containers without decoding them:
+    ///
+    /// ```
+    /// use binn_rs::{Object, Value};
+    ///
+    /// let mut buf = [0; 32];
+    /// let mut src = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    /// src.add_value("id", 1234u16).unwrap();
+    /// src.add_value("name", "sensor").unwrap();
+    ///
+    /// let mut buf = [0; 32];
+    /// let mut dst = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    /// for (key, _, raw, _) in src.iter_raw() {
+    ///     dst.add_raw(key, raw).unwrap();
+    /// }
+    /// assert_eq!(dst, src);
+    ///
+    /// assert!(dst.add_raw("extra", &[0x20]).is_err());
+    /// ```
+    pub fn add_raw(&mut self, key: &str, raw: &[u8]) -> Result<()> {
+        add_raw(&mut self.inner, Key::Str(key), raw)
+    }
+
+    /// Adds new list with given name and fills it with given closure
+    ///
+    /// See [`add_object`](Self::add_object) for details
+    pub fn add_list(
+        &mut self,
+        key: &str,
+        build: impl FnOnce(&mut List<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_list(&mut self.inner, Key::Str(key), build)
+    }
+
+    /// Adds new map with given name and fills it with given closure
+    ///
+    /// See [`add_object`](Self::add_object) for details
+    pub fn add_map(
+        &mut self,
+        key: &str,
+        build: impl FnOnce(&mut Map<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_map(&mut self.inner, Key::Str(key), build)
+    }
+
+    /// Adds new object with given name and fills it with given closure
+    ///
+    /// Nested object is built directly in buffer of this object, so no
+    /// temporary buffer is needed. If closure returns error, it's returned
+    /// from this method, but items added so far are kept.
+    ///
+    /// ```
+    /// use binn_rs::{Object, Value};
+    ///
+    /// let mut buf = [0; 64];
+    /// let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    /// obj.add_object("sensor", |sensor| {
+    ///     sensor.add_value("id", 5u8)?;
+    ///     sensor.add_list("values", |values| {
+    ///         values.add_value(1.5f32)?;
+    ///         values.add_value(2.5f32)?;
+    ///         Ok(())
+    ///     })
+    /// })
+    /// .unwrap();
+    ///
+    /// let sensor: Object = obj.get("sensor").unwrap().try_into().unwrap();
+    /// assert_eq!(sensor.get("id"), Some(Value::UInt8(5)));
+    /// ```
+    pub fn add_object(
+        &mut self,
+        key: &str,
+        build: impl FnOnce(&mut Object<'_>) -> Result<()>,
+    ) -> Result<()> {
+        add_object(&mut self.inner, Key::Str(key), build)
+    }
+
     /// Returns slice of bytes representing current document.
     ///
     /// It's guaranteed to be subslice of initial buffer
@@ -153,75 +724,388 @@ impl<'a> Object<'a> {
         self.inner.as_bytes()
     }
 
+    /// Enables or disables compaction of integers added to this object
+    ///
+    /// See [`List::set_compact_ints`]
+    pub fn set_compact_ints(&mut self, compact: bool) {
+        self.inner.set_compact_ints(compact);
+    }
+
+    /// Returns whether integers added to this object are compacted
+    pub fn compact_ints(&self) -> bool {
+        self.inner.compact_ints()
+    }
+
+    /// Writes canonical form of this object into given allocation
+    ///
+    /// Canonical form is deterministic, so semantically equal documents
+    /// produce the same bytes:
+    /// * items of objects are sorted by their keys (compared as bytes)
+    /// * items of maps are sorted by their keys (compared as numbers)
+    /// * items of lists keep their order
+    /// * integers of any width (including 128 bits) are stored in smallest type
+    ///   that can hold them, unsigned types are used for non-negative numbers and
+    ///   signed for negative
+    /// * sizes use compact form whenever possible
+    ///
+    /// Items with equal keys keep their relative order. User-defined containers
+    /// are copied as is since their keys are not known.
+    ///
+    /// Sorting doesn't require any allocations but takes O(n^2) time
+    pub fn to_canonical<'b>(&self, allocation: impl Into<Allocation<'b>>) -> Result<Object<'b>> {
+        let mut canonical = Object::empty_mut_with_subtype(self.subtype(), allocation)?;
+        canonical::write_items(&self.inner, &mut canonical.inner)?;
+
+        Ok(canonical)
+    }
+
     /// Returns number of elements in this object
     pub fn count(&self) -> usize {
         self.inner.count()
     }
 
+    /// Returns sub type of this object
+    ///
+    /// It's different from predefined one only for user-defined containers
+    pub fn subtype(&self) -> SubType {
+        self.inner.data_type().subtype
+    }
+
     /// Returns new empty object
     ///
     /// Object is read only so no new elements can be added to it
-    pub fn empty() -> Object<'static> {
-        // EMPTY_OBJ can't be malformed
-        Object {
-            inner: RawContainer::from_bytes(EMPTY_OBJ, KeyType::Str).unwrap(),
-        }
+    pub const fn empty() -> Object<'static> {
+        Object::from_static(EMPTY_OBJ)
     }
 
     /// Creates a new object that uses given allocation for storage
     pub fn empty_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
         Ok(Self {
-            inner: empty_mut(allocation.into(), EMPTY_OBJ, KeyType::Str)?,
+            inner: empty_mut(allocation.into(), data_type::OBJECT, KeyType::Str)?,
+        })
+    }
+
+    /// Creates a new object of user-defined type that uses given allocation for storage
+    ///
+    /// Sub types 0..=2 are reserved for standard containers, so
+    /// [`Error::TypeMismatch`] is returned for them, except for 2 which creates
+    /// standard object
+    pub fn empty_mut_with_subtype(
+        subtype: SubType,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: empty_mut(
+                allocation.into(),
+                container_type(subtype, data_type::OBJECT)?,
+                KeyType::Str,
+            )?,
+        })
+    }
+
+    /// Opens object that is already serialized in given allocation for modification
+    ///
+    /// See [`List::open_mut`] for details
+    pub fn open_mut(allocation: impl Into<Allocation<'a>>) -> Result<Self> {
+        Ok(Self {
+            inner: open_mut(allocation.into(), data_type::OBJECT, KeyType::Str)?,
+        })
+    }
+
+    /// Reads object serialized at start of given bytes
+    ///
+    /// See [`List::from_bytes`] for details
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            inner: from_bytes(bytes, data_type::OBJECT, KeyType::Str)?,
         })
     }
 
+    /// Reads object serialized at start of given static bytes in const context
+    ///
+    /// Works like [`from_bytes`](Self::from_bytes), but when used in constant
+    /// bytes are checked at compile time, so read-only handle costs nothing
+    /// at runtime. It can be used with bytes from [`ConstObject`](crate::ConstObject).
+    ///
+    /// ```
+    /// use binn_rs::{Object, Value};
+    ///
+    /// const BYTES: &[u8] = &[0xE2, 0x07, 0x01, 0x02, b'o', b'k', 0x01];
+    /// const OBJ: Object<'static> = Object::from_static(BYTES);
+    ///
+    /// assert_eq!(OBJ.get("ok"), Some(Value::True));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in const context) if bytes don't start
+    /// with valid object (standard or of user-defined type)
+    pub const fn from_static(bytes: &'static [u8]) -> Object<'static> {
+        Object {
+            inner: from_static(bytes, data_type::OBJECT, KeyType::Str),
+        }
+    }
+
     /// Get value with specific key
     pub fn get(&self, key: &str) -> Option<Value<'_>> {
         self.inner.get(Key::Str(key))
     }
 
+    /// Get serialized value with specific key without decoding it
+    pub fn get_raw(&self, key: &str) -> Option<&RawValue> {
+        self.inner.get_raw(Key::Str(key))
+    }
+
+    /// Get value with specific key converted to given type
+    ///
+    /// Returns `None` if there is no such value or it can't be converted
+    pub fn get_as<'b, T: TryFrom<Value<'b>>>(&'b self, key: &str) -> Option<T> {
+        self.get(key).and_then(|v| v.try_into().ok())
+    }
+
     /// Iterate over elements of this object
-    pub fn iter(&self) -> impl Iterator<Item = (&str, Value<'_>)> {
-        self.inner.iter().map(|(k, v)| (k.to_str().unwrap(), v))
+    pub fn iter(&self) -> ObjectIter<'_> {
+        ObjectIter::new(self.inner.iter())
+    }
+
+    /// Iterate over elements of this object starting at given position
+    ///
+    /// See [`List::iter_from`] for details
+    pub fn iter_from(&self, position: Position) -> Option<ObjectIter<'_>> {
+        self.inner.iter_at(position).map(ObjectIter::new)
+    }
+
+    /// Iterate over elements of this object with their serialized form
+    ///
+    /// See [`List::iter_raw`] for details
+    pub fn iter_raw(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&str, usize, &[u8], Value<'_>)> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(key, offset, raw)| {
+            (
+                key.to_str().unwrap(),
+                offset,
+                raw,
+                Value::deserialize_valid(raw),
+            )
+        })
+    }
+
+    /// Iterate over elements of this object without decoding them
+    ///
+    /// See [`List::iter_lazy`] for details
+    pub fn iter_lazy(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (&str, &RawValue)> + ExactSizeIterator {
+        let items = self.inner.iter().raw_items();
+        items.map(|(key, _, raw)| (key.to_str().unwrap(), RawValue::new_unchecked(raw)))
+    }
+
+    /// Iterate over keys of this object without decoding values
+    ///
+    /// ```
+    /// use binn_rs::binn;
+    ///
+    /// let mut buf = [0; 64];
+    /// let obj = binn!(buf.as_mut_slice(), {
+    ///     "id": 1u8,
+    ///     "tags": ["a", "b"],
+    /// })
+    /// .unwrap();
+    ///
+    /// assert!(obj.keys().eq(["id", "tags"]));
+    /// ```
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.inner.iter().keys().map(|key| key.to_str().unwrap())
     }
 }
 
-/// Helper function to create mutable container from given source
-fn empty_mut<'a>(
+/// Returns container type with given sub type
+///
+/// Sub types 0..=2 are reserved for standard list, map and object, so only
+/// the one that matches `own` type can be used
+fn container_type(subtype: SubType, own: Type) -> Result<Type> {
+    let data_type = Type {
+        storage: Storage::Container,
+        subtype,
+    };
+
+    let reserved = [data_type::LIST, data_type::MAP, data_type::OBJECT];
+    if data_type != own && reserved.contains(&data_type) {
+        return Err(Error::TypeMismatch);
+    }
+
+    Ok(data_type)
+}
+
+/// Helper function to add new list and fill it with closure
+fn add_list(
+    parent: &mut RawContainer<'_>,
+    key: Key<'_>,
+    build: impl FnOnce(&mut List<'_>) -> Result<()>,
+) -> Result<()> {
+    let inner = parent.add_empty_container(key, data_type::LIST, KeyType::Empty)?;
+    build(&mut List { inner })
+}
+
+/// Helper function to add new map and fill it with closure
+fn add_map(
+    parent: &mut RawContainer<'_>,
+    key: Key<'_>,
+    build: impl FnOnce(&mut Map<'_>) -> Result<()>,
+) -> Result<()> {
+    let inner = parent.add_empty_container(key, data_type::MAP, KeyType::Num)?;
+    build(&mut Map { inner })
+}
+
+/// Helper function to add new object and fill it with closure
+fn add_object(
+    parent: &mut RawContainer<'_>,
+    key: Key<'_>,
+    build: impl FnOnce(&mut Object<'_>) -> Result<()>,
+) -> Result<()> {
+    let inner = parent.add_empty_container(key, data_type::OBJECT, KeyType::Str)?;
+    build(&mut Object { inner })
+}
+
+/// Helper function to validate and add serialized value
+fn add_raw(parent: &mut RawContainer<'_>, key: Key<'_>, raw: &[u8]) -> Result<()> {
+    // bytes must contain single value without anything after it
+    if RawValue::from_bytes(raw)?.as_bytes().len() != raw.len() {
+        return Err(Error::Malformed);
+    }
+
+    parent.add_raw(key, raw)
+}
+
+/// Helper function to create empty mutable container of given type
+pub(crate) fn empty_mut<'a>(
     mut allocation: Allocation<'a>,
-    source: &[u8],
+    data_type: Type,
     key_type: KeyType,
 ) -> Result<RawContainer<'a>> {
-    let len = source.len();
+    // header consists of [type], [size] and [count]
+    let len = data_type.size() + 2;
     match &mut allocation {
         Allocation::Static(buf) => {
             if buf.len() < len {
                 return Err(Error::SmallBuffer(len - buf.len()));
             }
-            buf[..len].copy_from_slice(source);
+            let buf = data_type.write(buf);
+            let buf = Size::new(len).unwrap().write(buf)?;
+            Size::new(0).unwrap().write(buf)?;
         }
     }
 
     Ok(RawContainer::new_mut(allocation, key_type).unwrap())
 }
 
+/// Helper function to open existing mutable container of given type
+///
+/// User-defined containers can be opened with any key type
+fn open_mut<'a>(
+    allocation: Allocation<'a>,
+    data_type: Type,
+    key_type: KeyType,
+) -> Result<RawContainer<'a>> {
+    let actual = match &allocation {
+        Allocation::Static(buf) => Type::try_from(&buf[..])?,
+    };
+    match actual {
+        data_type::LIST | data_type::MAP | data_type::OBJECT if actual != data_type => {
+            Err(Error::TypeMismatch)
+        }
+        _ => RawContainer::new_mut(allocation, key_type),
+    }
+}
+
+/// Helper function to read existing container of given type in const context
+///
+/// User-defined containers can be read with any key type
+const fn from_static(
+    bytes: &'static [u8],
+    data_type: Type,
+    key_type: KeyType,
+) -> RawContainer<'static> {
+    let actual = match Type::parse(bytes) {
+        Ok(actual) => actual,
+        Err(_) => panic!("container is malformed"),
+    };
+    match actual {
+        data_type::LIST | data_type::MAP | data_type::OBJECT
+            if actual.subtype.value() != data_type.subtype.value() =>
+        {
+            panic!("bytes start with container of other type")
+        }
+        _ if !matches!(actual.storage, Storage::Container) => {
+            panic!("bytes don't start with container")
+        }
+        _ => RawContainer::from_static(bytes, key_type),
+    }
+}
+
+/// Helper function to read existing container of given type
+///
+/// User-defined containers can be read with any key type
+fn from_bytes(bytes: &[u8], data_type: Type, key_type: KeyType) -> Result<RawContainer<'_>> {
+    let actual = Type::try_from(bytes)?;
+    match actual {
+        data_type::LIST | data_type::MAP | data_type::OBJECT if actual != data_type => {
+            Err(Error::TypeMismatch)
+        }
+        _ if actual.storage != Storage::Container => Err(Error::TypeMismatch),
+        _ => RawContainer::from_bytes(bytes, key_type),
+    }
+}
+
 impl<'a> From<List<'a>> for Value<'a> {
     fn from(value: List<'a>) -> Self {
-        let inner = value.inner.clone();
-        Value::List(List { inner })
+        Value::List(value)
     }
 }
 
 impl<'a> From<Map<'a>> for Value<'a> {
     fn from(value: Map<'a>) -> Self {
-        let inner = value.inner.clone();
-        Value::Map(Map { inner })
+        Value::Map(value)
+    }
+}
+
+impl<'a> From<UserContainer<'a>> for Value<'a> {
+    fn from(value: UserContainer<'a>) -> Self {
+        Value::UserContainer(value)
     }
 }
 
 impl<'a> From<Object<'a>> for Value<'a> {
     fn from(value: Object<'a>) -> Self {
-        let inner = value.inner.clone();
-        Value::Object(Object { inner })
+        Value::Object(value)
+    }
+}
+
+/// Returns null if there is no item at given position
+impl Index<usize> for List<'_> {
+    type Output = RawValue;
+
+    fn index(&self, pos: usize) -> &RawValue {
+        self.get_raw(pos).unwrap_or(RawValue::NULL)
+    }
+}
+
+/// Returns null if there is no item with given key
+impl Index<i32> for Map<'_> {
+    type Output = RawValue;
+
+    fn index(&self, key: i32) -> &RawValue {
+        self.get_raw(key).unwrap_or(RawValue::NULL)
+    }
+}
+
+/// Returns null if there is no item with given key
+impl Index<&str> for Object<'_> {
+    type Output = RawValue;
+
+    fn index(&self, key: &str) -> &RawValue {
+        self.get_raw(key).unwrap_or(RawValue::NULL)
     }
 }
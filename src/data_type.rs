@@ -1,11 +1,10 @@
 use crate::storage::Storage;
 use crate::subtype::SubType;
 use crate::Error;
-use byteorder::{BigEndian, ByteOrder};
 
 use crate::error::Result;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Type {
     pub storage: Storage,
     pub subtype: SubType,
@@ -13,12 +12,12 @@ pub struct Type {
 
 impl Type {
     /// Returns whether this type is represented as single byte or not
-    pub fn is_u8(&self) -> bool {
+    pub const fn is_u8(&self) -> bool {
         self.subtype.value() < 16
     }
 
     /// Returns how many bytes this type will take (1 or 2)
-    pub fn size(&self) -> usize {
+    pub const fn size(&self) -> usize {
         if self.is_u8() {
             1
         } else {
@@ -26,21 +25,51 @@ impl Type {
         }
     }
 
+    /// Parses type at the beginning of given bytes
+    pub const fn parse(value: &[u8]) -> Result<Self> {
+        if value.is_empty() {
+            return Err(Error::Malformed);
+        }
+
+        let is_u8 = (value[0] & 0x10) == 0;
+
+        // both storage and subtype
+        let storage = match Storage::from_u8(value[0] & 0xE0) {
+            Some(storage) => storage,
+            None => unreachable!(),
+        };
+        let subtype = if is_u8 {
+            value[0] as u16 & 0x0F
+        } else {
+            if value.len() == 1 {
+                return Err(Error::Malformed);
+            }
+            ((value[0] as u16 & 0x0F) << 8) | value[1] as u16
+        };
+
+        Ok(Self {
+            storage,
+            subtype: SubType::new(subtype),
+        })
+    }
+
     /// Writes this type into buffer and returns new insert position
     ///
     /// # Panics:
     ///
     /// Panics if buffer is too short (less than 2 bytes)
-    pub fn write<'a>(&self, buf: &'a mut [u8]) -> &'a mut [u8] {
+    pub const fn write<'a>(&self, buf: &'a mut [u8]) -> &'a mut [u8] {
         let storage = self.storage as u16;
         let subtype = self.subtype.value();
 
         if self.is_u8() {
             buf[0] = (storage | subtype) as u8;
-            &mut buf[1..]
+            buf.split_at_mut(1).1
         } else {
-            BigEndian::write_u16(buf, (storage << 8) | 0x1000 | subtype);
-            &mut buf[2..]
+            let [high, low] = ((storage << 8) | 0x1000 | subtype).to_be_bytes();
+            buf[0] = high;
+            buf[1] = low;
+            buf.split_at_mut(2).1
         }
     }
 }
@@ -49,26 +78,7 @@ impl TryFrom<&[u8]> for Type {
     type Error = Error;
 
     fn try_from(value: &[u8]) -> Result<Self> {
-        if value.is_empty() {
-            return Err(Error::Malformed);
-        }
-
-        let is_u8 = (value[0] & 0x10) == 0;
-
-        // both storage and subtype
-        let storage = (value[0] & 0xE0).try_into().unwrap();
-        let subtype = if is_u8 {
-            value[0] as u16 & 0x0F
-        } else {
-            if value.len() == 1 {
-                return Err(Error::Malformed);
-            }
-            ((value[0] as u16 & 0x0F) << 8) | value[1] as u16
-        }
-        .try_into()
-        .unwrap();
-
-        Ok(Self { storage, subtype })
+        Self::parse(value)
     }
 }
 
@@ -167,6 +177,16 @@ pub const BLOB: Type = Type {
     subtype: SubType(0),
 };
 
+pub const UINT128: Type = Type {
+    storage: Storage::Blob,
+    subtype: SubType(128),
+};
+
+pub const INT128: Type = Type {
+    storage: Storage::Blob,
+    subtype: SubType(129),
+};
+
 pub const LIST: Type = Type {
     storage: Storage::Container,
     subtype: SubType(0),
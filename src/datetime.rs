@@ -0,0 +1,215 @@
+//! Parsing and formatting of text used by `DateTime`, `Date` and `Time` values
+//!
+//! Binn itself doesn't specify format of these strings, so ISO 8601
+//! (in form used by SQLite) is used:
+//! * date - `YYYY-MM-DD`
+//! * time - `HH:MM:SS[.fraction]`
+//! * datetime - `YYYY-MM-DD HH:MM:SS[.fraction]` (`T` is also accepted as separator)
+//!
+//! Years outside of `0000..=9999` are written with sign and at least 4 digits.
+//! Fraction of second is written only when it's not zero and can have up to 9 digits.
+use crate::error::{Error, Result};
+use core::fmt::Write;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Time of day, `second` can be 60 to represent leap second
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+impl Date {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser::new(text);
+        let date = parser.date()?;
+        parser.end()?;
+        Ok(date)
+    }
+
+    pub fn write(&self, out: &mut impl Write) -> Result<()> {
+        let res = if (0..=9999).contains(&self.year) {
+            write!(out, "{:04}", self.year)
+        } else {
+            write!(out, "{:+05}", self.year)
+        };
+        res.and_then(|_| write!(out, "-{:02}-{:02}", self.month, self.day))
+            .map_err(|_| Error::InvalidFormat)
+    }
+}
+
+impl Time {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser::new(text);
+        let time = parser.time()?;
+        parser.end()?;
+        Ok(time)
+    }
+
+    pub fn write(&self, out: &mut impl Write) -> Result<()> {
+        write!(
+            out,
+            "{:02}:{:02}:{:02}",
+            self.hour, self.minute, self.second
+        )
+        .map_err(|_| Error::InvalidFormat)?;
+
+        if self.nanosecond > 0 {
+            let mut fraction = self.nanosecond;
+            let mut digits = 9;
+            while fraction % 10 == 0 {
+                fraction /= 10;
+                digits -= 1;
+            }
+            write!(out, ".{:0width$}", fraction, width = digits)
+                .map_err(|_| Error::InvalidFormat)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_date_time(text: &str) -> Result<(Date, Time)> {
+    let mut parser = Parser::new(text);
+    let date = parser.date()?;
+    match parser.next() {
+        Some(b' ') | Some(b'T') => {}
+        _ => return Err(Error::InvalidFormat),
+    }
+    let time = parser.time()?;
+    parser.end()?;
+
+    Ok((date, time))
+}
+
+pub fn write_date_time(date: &Date, time: &Time, out: &mut impl Write) -> Result<()> {
+    date.write(out)?;
+    out.write_char(' ').map_err(|_| Error::InvalidFormat)?;
+    time.write(out)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn date(&mut self) -> Result<Date> {
+        let negative = match self.peek() {
+            Some(b'-') => true,
+            Some(b'+') => false,
+            _ => {
+                let year = self.digits(4, 4)? as i32;
+                return self.month_and_day(year);
+            }
+        };
+        self.pos += 1;
+        let year = self.digits(4, 9)? as i32;
+        let year = if negative { -year } else { year };
+
+        self.month_and_day(year)
+    }
+
+    fn month_and_day(&mut self, year: i32) -> Result<Date> {
+        self.expect(b'-')?;
+        let month = self.digits(2, 2)? as u8;
+        self.expect(b'-')?;
+        let day = self.digits(2, 2)? as u8;
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(Error::InvalidFormat);
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    fn time(&mut self) -> Result<Time> {
+        let hour = self.digits(2, 2)? as u8;
+        self.expect(b':')?;
+        let minute = self.digits(2, 2)? as u8;
+        self.expect(b':')?;
+        let second = self.digits(2, 2)? as u8;
+
+        let nanosecond = if self.peek() == Some(b'.') {
+            self.pos += 1;
+            let start = self.pos;
+            let fraction = self.digits(1, 9)?;
+            (fraction * 10u64.pow(9 - (self.pos - start) as u32)) as u32
+        } else {
+            0
+        };
+
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(Error::InvalidFormat);
+        }
+
+        Ok(Time {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    }
+
+    /// Reads decimal number that has from `min` to `max` digits
+    fn digits(&mut self, min: usize, max: usize) -> Result<u64> {
+        let mut value = 0u64;
+        let start = self.pos;
+        while self.pos - start < max {
+            match self.peek() {
+                Some(c @ b'0'..=b'9') => {
+                    value = value * 10 + (c - b'0') as u64;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+
+        if self.pos - start < min {
+            Err(Error::InvalidFormat)
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn end(&self) -> Result<()> {
+        if self.pos == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(Error::InvalidFormat)
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        if self.next() == Some(c) {
+            Ok(())
+        } else {
+            Err(Error::InvalidFormat)
+        }
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+}
@@ -0,0 +1,25 @@
+use crate::error::Error;
+use crate::text_value::{TextKind, TextValue};
+use crate::Value;
+use core::fmt::Write;
+use rust_decimal::Decimal;
+
+impl From<Decimal> for TextValue {
+    fn from(value: Decimal) -> Self {
+        TextValue::format(TextKind::DecimalStr, |text| {
+            write!(text, "{}", value).map_err(|_| Error::InvalidFormat)
+        })
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for Decimal {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::DecimalStr(text) = value {
+            Decimal::from_str_exact(text).map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
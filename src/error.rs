@@ -3,18 +3,29 @@ pub type Result<T> = core::result::Result<T, Error>;
 /// Error that might occur when using binn values
 #[derive(Debug)]
 pub enum Error {
+    /// Text value doesn't follow expected format (e.g. date or decimal
+    /// number that can't be parsed)
+    InvalidFormat,
+
     /// Attempted to insert value with key longer than 255 bytes
     LongKey,
 
     /// Given byte buffer was malformed and couldn't be parsed
     Malformed,
 
+    /// Patch operation couldn't be applied to document (e.g. removed key
+    /// doesn't exist or added key already exists)
+    PatchConflict,
+
     /// Container was read only and cannot be modified
     ReadOnly,
 
     /// Indicates that static buffer was not big enough and contains
     /// how many extra bytes are needed
     SmallBuffer(usize),
+
+    /// Value has different type than was requested
+    TypeMismatch,
 }
 
 #[derive(Debug)]
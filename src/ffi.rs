@@ -0,0 +1,577 @@
+//! C ABI compatible with [reference implementation](https://github.com/liteserver/binn)
+//!
+//! Functions mirror subset of `binn.h` API, so C code can link this crate
+//! (e.g. built with `cargo rustc --release --features ffi --crate-type staticlib`)
+//! instead of original library. Header is generated with `cbindgen` and
+//! is located at `include/binn.h`.
+//!
+//! Containers are created with [`binn_list`], [`binn_map`] and [`binn_object`],
+//! they grow automatically and must be released with [`binn_free`]. Integers
+//! are written with the smallest type that can hold them, as reference
+//! implementation does.
+//!
+//! Read functions (`binn_size`, `binn_*_get_*`, etc.) accept either a created
+//! container or a pointer to serialized container (e.g. one received from
+//! network or returned by [`binn_ptr`]). Positions in lists start from 1.
+//! Functions that return `BOOL` return `TRUE` (1) on success and `FALSE` (0)
+//! otherwise.
+//!
+//! # Safety
+//!
+//! All pointers must be either null or valid: containers must be created by
+//! this module and not yet freed, serialized containers must be readable for
+//! their whole size, strings must be null terminated. Pointers returned by
+//! read functions point inside container and are valid until it is modified
+//! or freed.
+use crate::container;
+use crate::data_type::{self, Type};
+use crate::error::Result;
+use crate::raw_container::{Key, KeyType, RawContainer};
+use crate::storage::Storage;
+use crate::{Allocation, Error, List, Map, Object, Value};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use byteorder::{BigEndian, ByteOrder};
+use core::ffi::{c_char, c_int, c_void, CStr};
+use core::slice;
+use paste::paste;
+
+/// Type of list container, as returned by [`binn_type`]
+pub const BINN_LIST: c_int = 0xE0;
+/// Type of map container, as returned by [`binn_type`]
+pub const BINN_MAP: c_int = 0xE1;
+/// Type of object container, as returned by [`binn_type`]
+pub const BINN_OBJECT: c_int = 0xE2;
+
+/// Distinguishes created containers from serialized ones
+const MAGIC: u32 = 0x1F22B11F;
+
+/// Initial size of buffer of created container
+const INITIAL_SIZE: usize = 256;
+
+const TRUE: c_int = 1;
+const FALSE: c_int = 0;
+
+/// Container that owns its storage, C code sees it as opaque `binn` type
+#[repr(C)]
+#[derive(Debug)]
+pub struct Binn {
+    magic: u32,
+    data: Vec<u8>,
+}
+
+/// Item of container that should be read
+enum Lookup<'a> {
+    /// Position in list, starting from 1
+    Pos(c_int),
+    Key(Key<'a>),
+}
+
+/// Creates new empty list
+#[no_mangle]
+pub extern "C" fn binn_list() -> *mut Binn {
+    new_container(data_type::LIST)
+}
+
+/// Creates new empty map
+#[no_mangle]
+pub extern "C" fn binn_map() -> *mut Binn {
+    new_container(data_type::MAP)
+}
+
+/// Creates new empty object
+#[no_mangle]
+pub extern "C" fn binn_object() -> *mut Binn {
+    new_container(data_type::OBJECT)
+}
+
+/// Releases container created by this module, does nothing for other pointers
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_free(item: *mut Binn) {
+    if handle(item).is_some() {
+        drop(Box::from_raw(item));
+    }
+}
+
+/// Returns pointer to serialized container or null if it is not valid
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_ptr(ptr: *mut c_void) -> *mut c_void {
+    match read_container(ptr) {
+        Some(container) => container.as_bytes().as_ptr() as *mut c_void,
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Returns size of serialized container or -1 if it is not valid
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_size(ptr: *mut c_void) -> c_int {
+    read_container(ptr).map_or(-1, |container| container.as_bytes().len() as c_int)
+}
+
+/// Returns number of items in container or -1 if it is not valid
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_count(ptr: *mut c_void) -> c_int {
+    read_container(ptr).map_or(-1, |container| container.count() as c_int)
+}
+
+/// Returns type of container ([`BINN_LIST`], [`BINN_MAP`] or [`BINN_OBJECT`])
+/// or -1 if it is not valid
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_type(ptr: *mut c_void) -> c_int {
+    match read_container(ptr).map(|container| container.key_type()) {
+        Some(KeyType::Empty) => BINN_LIST,
+        Some(KeyType::Num) => BINN_MAP,
+        Some(KeyType::Str) => BINN_OBJECT,
+        _ => -1,
+    }
+}
+
+/// Adds null value to the end of list
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_list_add_null(list: *mut Binn) -> c_int {
+    write(list, Some(Key::Empty), Some(Value::Null))
+}
+
+/// Adds null value with given id to map
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_map_set_null(map: *mut Binn, id: c_int) -> c_int {
+    write(map, Some(Key::Num(id)), Some(Value::Null))
+}
+
+/// Adds null value with given key to object
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_object_set_null(obj: *mut Binn, key: *const c_char) -> c_int {
+    write(obj, c_str(key).map(Key::Str), Some(Value::Null))
+}
+
+/// Adds blob of given size to the end of list
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_list_add_blob(
+    list: *mut Binn,
+    ptr: *mut c_void,
+    size: c_int,
+) -> c_int {
+    write(list, Some(Key::Empty), blob(ptr, size))
+}
+
+/// Adds blob of given size with given id to map
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_map_set_blob(
+    map: *mut Binn,
+    id: c_int,
+    ptr: *mut c_void,
+    size: c_int,
+) -> c_int {
+    write(map, Some(Key::Num(id)), blob(ptr, size))
+}
+
+/// Adds blob of given size with given key to object
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_object_set_blob(
+    obj: *mut Binn,
+    key: *const c_char,
+    ptr: *mut c_void,
+    size: c_int,
+) -> c_int {
+    write(obj, c_str(key).map(Key::Str), blob(ptr, size))
+}
+
+/// Reads blob at given position (starting from 1) of list
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_list_get_blob(
+    list: *mut c_void,
+    pos: c_int,
+    pvalue: *mut *mut c_void,
+    psize: *mut c_int,
+) -> c_int {
+    read_blob(list, Some(Lookup::Pos(pos)), pvalue, psize)
+}
+
+/// Reads blob with given id from map
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_map_get_blob(
+    map: *mut c_void,
+    id: c_int,
+    pvalue: *mut *mut c_void,
+    psize: *mut c_int,
+) -> c_int {
+    read_blob(map, Some(Lookup::Key(Key::Num(id))), pvalue, psize)
+}
+
+/// Reads blob with given key from object
+///
+/// # Safety
+///
+/// See [module documentation](self)
+#[no_mangle]
+pub unsafe extern "C" fn binn_object_get_blob(
+    obj: *mut c_void,
+    key: *const c_char,
+    pvalue: *mut *mut c_void,
+    psize: *mut c_int,
+) -> c_int {
+    let lookup = c_str(key).map(|key| Lookup::Key(Key::Str(key)));
+    read_blob(obj, lookup, pvalue, psize)
+}
+
+/// Generates functions to add and read values of given C type.
+///
+/// For each type there is conversion to `Option<Value>` and
+/// conversion from `Value` to `Option` of C type.
+macro_rules! ffi_impl {
+    ($($name:ident($c_type:ty): $to_value:expr, $from_value:expr;)+) => {
+        paste! {
+            $(
+                #[doc = concat!(" Adds `", stringify!($name), "` value to the end of list")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_list_add_ $name>](
+                    list: *mut Binn,
+                    value: $c_type,
+                ) -> c_int {
+                    write(list, Some(Key::Empty), ($to_value)(value))
+                }
+
+                #[doc = concat!(" Adds `", stringify!($name), "` value with given id to map")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_map_set_ $name>](
+                    map: *mut Binn,
+                    id: c_int,
+                    value: $c_type,
+                ) -> c_int {
+                    write(map, Some(Key::Num(id)), ($to_value)(value))
+                }
+
+                #[doc = concat!(" Adds `", stringify!($name), "` value with given key to object")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_object_set_ $name>](
+                    obj: *mut Binn,
+                    key: *const c_char,
+                    value: $c_type,
+                ) -> c_int {
+                    write(obj, c_str(key).map(Key::Str), ($to_value)(value))
+                }
+
+                #[doc = concat!(" Reads `", stringify!($name), "` value at given position (starting from 1) of list")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_list_get_ $name>](
+                    list: *mut c_void,
+                    pos: c_int,
+                    pvalue: *mut $c_type,
+                ) -> c_int {
+                    read(list, Some(Lookup::Pos(pos)), pvalue, $from_value)
+                }
+
+                #[doc = concat!(" Reads `", stringify!($name), "` value with given id from map")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_map_get_ $name>](
+                    map: *mut c_void,
+                    id: c_int,
+                    pvalue: *mut $c_type,
+                ) -> c_int {
+                    read(map, Some(Lookup::Key(Key::Num(id))), pvalue, $from_value)
+                }
+
+                #[doc = concat!(" Reads `", stringify!($name), "` value with given key from object")]
+                ///
+                /// # Safety
+                ///
+                /// See [module documentation](self)
+                #[no_mangle]
+                pub unsafe extern "C" fn [<binn_object_get_ $name>](
+                    obj: *mut c_void,
+                    key: *const c_char,
+                    pvalue: *mut $c_type,
+                ) -> c_int {
+                    let lookup = c_str(key).map(|key| Lookup::Key(Key::Str(key)));
+                    read(obj, lookup, pvalue, $from_value)
+                }
+            )+
+        }
+    };
+}
+
+ffi_impl! {
+    int8(i8): |v| Some(Value::Int8(v)), |v| v.try_into().ok();
+    int16(i16): |v| Some(Value::Int16(v)), |v| v.try_into().ok();
+    int32(i32): |v| Some(Value::Int32(v)), |v| v.try_into().ok();
+    int64(i64): |v| Some(Value::Int64(v)), |v| v.try_into().ok();
+    uint8(u8): |v| Some(Value::UInt8(v)), |v| v.try_into().ok();
+    uint16(u16): |v| Some(Value::UInt16(v)), |v| v.try_into().ok();
+    uint32(u32): |v| Some(Value::UInt32(v)), |v| v.try_into().ok();
+    uint64(u64): |v| Some(Value::UInt64(v)), |v| v.try_into().ok();
+    float(f32): |v| Some(Value::Float(v)), |v| match v {
+        Value::Float(v) => Some(v),
+        Value::Double(v) => Some(v as f32),
+        _ => None,
+    };
+    double(f64): |v| Some(Value::Double(v)), |v| match v {
+        Value::Float(v) => Some(v as f64),
+        Value::Double(v) => Some(v),
+        _ => None,
+    };
+    bool(c_int): |v| Some(Value::from(v != FALSE)), |v| match v {
+        Value::True => Some(TRUE),
+        Value::False => Some(FALSE),
+        _ => None,
+    };
+    str(*mut c_char): |v| c_str(v).map(Value::Text), |v| match v {
+        // text is always followed by null terminator
+        Value::Text(text) => Some(text.as_ptr() as *mut c_char),
+        _ => None,
+    };
+    list(*mut c_void): |v| container_value(v, KeyType::Empty), |v| match v {
+        Value::List(list) => Some(list.as_bytes().as_ptr() as *mut c_void),
+        _ => None,
+    };
+    map(*mut c_void): |v| container_value(v, KeyType::Num), |v| match v {
+        Value::Map(map) => Some(map.as_bytes().as_ptr() as *mut c_void),
+        _ => None,
+    };
+    object(*mut c_void): |v| container_value(v, KeyType::Str), |v| match v {
+        Value::Object(obj) => Some(obj.as_bytes().as_ptr() as *mut c_void),
+        _ => None,
+    };
+}
+
+fn new_container(data_type: Type) -> *mut Binn {
+    let mut data = vec![0; INITIAL_SIZE];
+    // initial size is enough for any container header
+    container::empty_mut(data.as_mut_slice().into(), data_type, KeyType::Unknown).unwrap();
+
+    Box::into_raw(Box::new(Binn { magic: MAGIC, data }))
+}
+
+/// Returns key type of list, map or object
+fn key_type(data_type: Type) -> Option<KeyType> {
+    match data_type {
+        data_type::LIST => Some(KeyType::Empty),
+        data_type::MAP => Some(KeyType::Num),
+        data_type::OBJECT => Some(KeyType::Str),
+        _ => None,
+    }
+}
+
+/// Returns container created by this module
+unsafe fn handle<'a>(item: *mut Binn) -> Option<&'a mut Binn> {
+    item.as_mut().filter(|item| item.magic == MAGIC)
+}
+
+/// Returns bytes of created or serialized container and whether they were
+/// created by this module
+///
+/// Returned slice might be bigger than container
+unsafe fn container_bytes<'a>(ptr: *mut c_void) -> Option<(&'a [u8], bool)> {
+    let ptr = ptr as *const u8;
+    if ptr.is_null() {
+        return None;
+    }
+    if Storage::try_from(*ptr & 0xE0) != Ok(Storage::Container) {
+        // first byte of magic is never a container type
+        return handle(ptr as *mut Binn).map(|item| (item.data.as_slice(), true));
+    }
+
+    // [type] might take one or two bytes
+    let size = ptr.add(if *ptr & 0x10 == 0 { 1 } else { 2 });
+    let size = if *size & 0x80 == 0 {
+        *size as usize
+    } else {
+        (BigEndian::read_u32(slice::from_raw_parts(size, 4)) & 0x7FFF_FFFF) as usize
+    };
+
+    Some((slice::from_raw_parts(ptr, size), false))
+}
+
+/// Returns list, map or object from created or serialized container
+unsafe fn read_container<'a>(ptr: *mut c_void) -> Option<RawContainer<'a>> {
+    let (bytes, created) = container_bytes(ptr)?;
+    let key_type = key_type(Type::try_from(bytes).ok()?)?;
+
+    if created {
+        // like in add_value, created container is trusted to be valid
+        Some(RawContainer::from_valid_bytes(bytes, key_type))
+    } else {
+        RawContainer::from_bytes(bytes, key_type).ok()
+    }
+}
+
+/// Returns container with given key type as value
+unsafe fn container_value<'a>(ptr: *mut c_void, key_type: KeyType) -> Option<Value<'a>> {
+    let inner = read_container(ptr).filter(|inner| inner.key_type() == key_type)?;
+
+    Some(match key_type {
+        KeyType::Empty => Value::List(List { inner }),
+        KeyType::Num => Value::Map(Map { inner }),
+        _ => Value::Object(Object { inner }),
+    })
+}
+
+unsafe fn c_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok()
+}
+
+unsafe fn blob<'a>(ptr: *mut c_void, size: c_int) -> Option<Value<'a>> {
+    let size = usize::try_from(size).ok()?;
+    if ptr.is_null() {
+        return (size == 0).then_some(Value::Blob(&[]));
+    }
+
+    Some(Value::Blob(slice::from_raw_parts(ptr as *const u8, size)))
+}
+
+/// Adds value to created container, storage is grown if needed
+unsafe fn write(item: *mut Binn, key: Option<Key<'_>>, value: Option<Value<'_>>) -> c_int {
+    let (Some(item), Some(key), Some(value)) = (handle(item), key, value) else {
+        return FALSE;
+    };
+
+    loop {
+        match add_value(&mut item.data, key, &value) {
+            Ok(()) => return TRUE,
+            Err(Error::SmallBuffer(extra)) => {
+                let len = item.data.len();
+                item.data.resize(len + extra.max(len), 0);
+            }
+            Err(_) => return FALSE,
+        }
+    }
+}
+
+fn add_value(data: &mut [u8], key: Key<'_>, value: &Value<'_>) -> Result<()> {
+    let key_type = key_type(Type::try_from(&*data)?).ok_or(Error::Malformed)?;
+    if key_type != key.key_type() {
+        return Err(Error::TypeMismatch);
+    }
+
+    // storage of created container is only written by this module, so it is
+    // not validated again and adding of each item doesn't rescan all items
+    let mut container = RawContainer::new_mut_valid(Allocation::Static(data), key_type);
+    container.set_compact_ints(true);
+    container.add_value(key, value.borrowed())?;
+
+    Ok(())
+}
+
+/// Reads item of container and writes it to `pvalue` if it has expected type
+unsafe fn read<T>(
+    ptr: *mut c_void,
+    lookup: Option<Lookup<'_>>,
+    pvalue: *mut T,
+    convert: impl FnOnce(Value<'_>) -> Option<T>,
+) -> c_int {
+    let (Some(container), Some(lookup)) = (read_container(ptr), lookup) else {
+        return FALSE;
+    };
+    let value = match lookup {
+        Lookup::Pos(pos) => usize::try_from(pos)
+            .ok()
+            .and_then(|pos| pos.checked_sub(1))
+            .and_then(|pos| container.get_at(pos)),
+        Lookup::Key(key) => container.get(key),
+    };
+
+    match value.and_then(convert) {
+        Some(value) if !pvalue.is_null() => {
+            pvalue.write(value);
+            TRUE
+        }
+        _ => FALSE,
+    }
+}
+
+unsafe fn read_blob(
+    ptr: *mut c_void,
+    lookup: Option<Lookup<'_>>,
+    pvalue: *mut *mut c_void,
+    psize: *mut c_int,
+) -> c_int {
+    let mut blob = (core::ptr::null_mut(), 0);
+    let found = read(ptr, lookup, &mut blob, |v| match v {
+        Value::Blob(blob) => Some((blob.as_ptr() as *mut c_void, blob.len() as c_int)),
+        _ => None,
+    });
+    if found == FALSE || pvalue.is_null() {
+        return FALSE;
+    }
+
+    pvalue.write(blob.0);
+    if !psize.is_null() {
+        psize.write(blob.1);
+    }
+    TRUE
+}
@@ -0,0 +1,76 @@
+use crate::raw_container::{Key, Position, RawIterator};
+use crate::Value;
+use core::iter::FusedIterator;
+
+macro_rules! container_iter_impl {
+    ($(#[$meta:meta])* $name:ident<$lt:lifetime>, $item:ty, $map:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        pub struct $name<$lt> {
+            inner: RawIterator<$lt>,
+        }
+
+        impl<$lt> $name<$lt> {
+            pub(crate) fn new(inner: RawIterator<$lt>) -> Self {
+                Self { inner }
+            }
+
+            /// Returns position of next item, which can be used to resume
+            /// iteration later
+            pub fn current_position(&self) -> Position {
+                self.inner.current_position()
+            }
+        }
+
+        impl<$lt> Iterator for $name<$lt> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.inner.next().map($map)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth(n).map($map)
+            }
+        }
+
+        impl<$lt> DoubleEndedIterator for $name<$lt> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.inner.next_back().map($map)
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.inner.nth_back(n).map($map)
+            }
+        }
+
+        impl ExactSizeIterator for $name<'_> {}
+
+        impl FusedIterator for $name<'_> {}
+    };
+}
+
+container_iter_impl!(
+    /// Iterator over values of [`List`](crate::List)
+    ListIter<'a>,
+    Value<'a>,
+    |(_, value): (Key<'_>, _)| value
+);
+
+container_iter_impl!(
+    /// Iterator over keys and values of [`Map`](crate::Map)
+    MapIter<'a>,
+    (i32, Value<'a>),
+    |(key, value): (Key<'_>, _)| (key.to_num().unwrap(), value)
+);
+
+container_iter_impl!(
+    /// Iterator over keys and values of [`Object`](crate::Object)
+    ObjectIter<'a>,
+    (&'a str, Value<'a>),
+    |(key, value): (Key<'a>, _)| (key.to_str().unwrap(), value)
+);
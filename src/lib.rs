@@ -60,20 +60,69 @@
 //!
 #![no_std]
 #![warn(rust_2018_idioms, missing_debug_implementations, missing_docs)]
+#![deny(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Works like `?` operator, but can be used in const functions
+macro_rules! tri {
+    ($result:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        }
+    };
+}
 
 mod allocation;
+mod canonical;
+#[cfg(feature = "chrono")]
+mod chrono_support;
+mod const_builder;
 mod container;
 mod data_type;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal_support;
 mod error;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+mod iter;
+mod macros;
+mod merge;
+mod patch;
 mod raw_container;
+mod raw_value;
+mod semantic;
 mod size;
 mod storage;
 mod subtype;
+#[cfg(any(feature = "chrono", feature = "time", feature = "rust_decimal"))]
+mod text_value;
+#[cfg(feature = "time")]
+mod time_support;
+mod user_type;
 mod utils;
 mod value;
 
 pub use allocation::Allocation;
-pub use container::{List, Map, Object};
+pub use const_builder::{ConstList, ConstMap, ConstObject};
+pub use container::{List, Map, Object, UserContainer};
 pub use error::Error;
+pub use iter::{ListIter, MapIter, ObjectIter};
+#[doc(hidden)]
+pub use macros::__private;
+pub use merge::ListMerge;
+pub use patch::{apply_patch, diff};
+pub use raw_container::Position;
+pub use raw_value::RawValue;
+pub use semantic::SemanticValue;
+pub use storage::Storage;
 pub use subtype::SubType;
+#[cfg(any(feature = "chrono", feature = "time", feature = "rust_decimal"))]
+pub use text_value::TextValue;
+pub use user_type::{BinnUserType, UserStorage};
 pub use value::Value;
@@ -0,0 +1,214 @@
+/// Writes binn document described by literal into given allocation
+///
+/// Syntax is similar to JSON: `[...]` creates list, `{...}` creates object
+/// when keys are strings or map when keys are `i32` numbers. `null` creates
+/// null value, all other values are converted with `Value::from`.
+///
+/// Returns created `List`, `Map` or `Object` or [`Error::SmallBuffer`](crate::Error::SmallBuffer)
+/// if allocation is not big enough:
+/// ```
+/// use binn_rs::{binn, List, Map, Value};
+///
+/// let mut buf = [0; 64];
+/// let obj = binn!(buf.as_mut_slice(), {
+///     "id": 5u16,
+///     "tags": ["a", "b"],
+///     "sensors": { 3: null, -1: Value::Date("2023-04-15") },
+/// })
+/// .unwrap();
+///
+/// assert_eq!(obj.get("id").unwrap(), Value::UInt16(5));
+///
+/// let tags: List = obj.get("tags").unwrap().try_into().unwrap();
+/// assert_eq!(tags.get(1).unwrap(), Value::Text("b"));
+///
+/// let sensors: Map = obj.get("sensors").unwrap().try_into().unwrap();
+/// assert_eq!(sensors.get(3).unwrap(), Value::Null);
+/// ```
+///
+/// Keys of single object or map must all be of the same type
+#[macro_export]
+macro_rules! binn {
+    ($allocation:expr, [$($items:tt)*]) => {{
+        let allocation = $allocation;
+        (move || -> ::core::result::Result<_, $crate::Error> {
+            let mut container = $crate::__private::root_list(allocation)?;
+            $crate::__binn_internal!(@list container () $($items)*);
+            Ok(container)
+        })()
+    }};
+
+    ($allocation:expr, {}) => {
+        $crate::Object::empty_mut($allocation)
+    };
+
+    ($allocation:expr, {$($items:tt)+}) => {{
+        let allocation = $allocation;
+        (move || -> ::core::result::Result<_, $crate::Error> {
+            let first = $crate::__binn_internal!(@first_key () $($items)+);
+            let mut container = $crate::__private::root(&first, allocation)?;
+            $crate::__binn_internal!(@keyed container () $($items)+);
+            Ok(container)
+        })()
+    }};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __binn_internal {
+    // Returns first key of object or map
+    (@first_key ($($key:tt)+) : $($rest:tt)*) => { $($key)+ };
+    (@first_key ($($key:tt)*) $t:tt $($rest:tt)*) => {
+        $crate::__binn_internal!(@first_key ($($key)* $t) $($rest)*)
+    };
+
+    // Items of object or map, key is accumulated until ':'
+    (@keyed $parent:ident ()) => {};
+    (@keyed $parent:ident ($($key:tt)+) : $($rest:tt)*) => {
+        $crate::__binn_internal!(@value $parent @keyed ($($key)+) () $($rest)*)
+    };
+    (@keyed $parent:ident ($($key:tt)*) $t:tt $($rest:tt)*) => {
+        $crate::__binn_internal!(@keyed $parent ($($key)* $t) $($rest)*)
+    };
+
+    // Items of list
+    (@list $parent:ident ()) => {};
+    (@list $parent:ident () $($rest:tt)+) => {
+        $crate::__binn_internal!(@value $parent @list (()) () $($rest)+)
+    };
+
+    // Single value, it is accumulated until ','
+    (@value $parent:ident @$next:ident ($($key:tt)+) () null $(, $($rest:tt)*)?) => {
+        $crate::__private::add(&mut $parent, $($key)+, $crate::Value::Null)?;
+        $crate::__binn_internal!(@$next $parent () $($($rest)*)?);
+    };
+    (@value $parent:ident @$next:ident ($($key:tt)+) () [$($items:tt)*] $(, $($rest:tt)*)?) => {
+        {
+            let mut child = $crate::__private::nested_list(&mut $parent, $($key)+)?;
+            $crate::__binn_internal!(@list child () $($items)*);
+        }
+        $crate::__binn_internal!(@$next $parent () $($($rest)*)?);
+    };
+    (@value $parent:ident @$next:ident ($($key:tt)+) () {} $(, $($rest:tt)*)?) => {
+        $crate::__private::add(&mut $parent, $($key)+, $crate::Object::empty())?;
+        $crate::__binn_internal!(@$next $parent () $($($rest)*)?);
+    };
+    (@value $parent:ident @$next:ident ($($key:tt)+) () {$($items:tt)+} $(, $($rest:tt)*)?) => {
+        {
+            let first = $crate::__binn_internal!(@first_key () $($items)+);
+            let mut child = $crate::__private::nested(&mut $parent, $($key)+, &first)?;
+            $crate::__binn_internal!(@keyed child () $($items)+);
+        }
+        $crate::__binn_internal!(@$next $parent () $($($rest)*)?);
+    };
+    (@value $parent:ident @$next:ident ($($key:tt)+) ($($value:tt)+) $(, $($rest:tt)*)?) => {
+        $crate::__private::add(&mut $parent, $($key)+, $crate::Value::from($($value)+))?;
+        $crate::__binn_internal!(@$next $parent () $($($rest)*)?);
+    };
+    (@value $parent:ident @$next:ident ($($key:tt)+) ($($value:tt)*) $t:tt $($rest:tt)*) => {
+        $crate::__binn_internal!(@value $parent @$next ($($key)+) ($($value)* $t) $($rest)*)
+    };
+}
+
+/// Helpers used by `binn!` macro
+#[doc(hidden)]
+pub mod __private {
+    use crate::error::Result;
+    use crate::{Allocation, List, Map, Object, Value};
+
+    /// Key of object or map, defines which container is created
+    pub trait MacroKey: Copy {
+        type Container<'a>: TryFrom<Value<'a>>;
+
+        fn empty() -> Value<'static>;
+
+        fn empty_mut(allocation: Allocation<'_>) -> Result<Self::Container<'_>>;
+    }
+
+    impl MacroKey for &str {
+        type Container<'a> = Object<'a>;
+
+        fn empty() -> Value<'static> {
+            Object::empty().into()
+        }
+
+        fn empty_mut(allocation: Allocation<'_>) -> Result<Object<'_>> {
+            Object::empty_mut(allocation)
+        }
+    }
+
+    impl MacroKey for i32 {
+        type Container<'a> = Map<'a>;
+
+        fn empty() -> Value<'static> {
+            Map::empty().into()
+        }
+
+        fn empty_mut(allocation: Allocation<'_>) -> Result<Map<'_>> {
+            Map::empty_mut(allocation)
+        }
+    }
+
+    /// Container that accepts values with key of type `K`
+    pub trait MacroParent<K> {
+        fn add_item<'c, 'p: 'c>(&'p mut self, key: K, value: Value<'_>) -> Result<Value<'c>>;
+    }
+
+    impl MacroParent<()> for List<'_> {
+        fn add_item<'c, 'p: 'c>(&'p mut self, _: (), value: Value<'_>) -> Result<Value<'c>> {
+            self.add_value(value)
+        }
+    }
+
+    impl MacroParent<i32> for Map<'_> {
+        fn add_item<'c, 'p: 'c>(&'p mut self, key: i32, value: Value<'_>) -> Result<Value<'c>> {
+            self.add_value(key, value)
+        }
+    }
+
+    impl MacroParent<&str> for Object<'_> {
+        fn add_item<'c, 'p: 'c>(&'p mut self, key: &str, value: Value<'_>) -> Result<Value<'c>> {
+            self.add_value(key, value)
+        }
+    }
+
+    pub fn add<'d, K, P: MacroParent<K>>(
+        parent: &mut P,
+        key: K,
+        value: impl Into<Value<'d>>,
+    ) -> Result<()> {
+        parent.add_item(key, value.into()).map(|_| ())
+    }
+
+    pub fn root<'a, K: MacroKey>(
+        _first: &K,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<K::Container<'a>> {
+        K::empty_mut(allocation.into())
+    }
+
+    pub fn root_list<'a>(allocation: impl Into<Allocation<'a>>) -> Result<List<'a>> {
+        List::empty_mut(allocation)
+    }
+
+    pub fn nested<'c, 'p: 'c, PK, P: MacroParent<PK>, K: MacroKey>(
+        parent: &'p mut P,
+        key: PK,
+        _first: &K,
+    ) -> Result<K::Container<'c>> {
+        match parent.add_item(key, K::empty())?.try_into() {
+            Ok(child) => Ok(child),
+            Err(_) => unreachable!("added container has the same type"),
+        }
+    }
+
+    pub fn nested_list<'c, 'p: 'c, PK, P: MacroParent<PK>>(
+        parent: &'p mut P,
+        key: PK,
+    ) -> Result<List<'c>> {
+        match parent.add_item(key, List::empty().into())? {
+            Value::List(child) => Ok(child),
+            _ => unreachable!("added container has the same type"),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Deep merge of objects
+use crate::error::Result;
+use crate::raw_container::{Key, RawContainer};
+use crate::{Allocation, Object, Value};
+
+/// Strategy of merging two lists that are stored under the same key
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListMerge {
+    /// List from overlay replaces list from base
+    #[default]
+    Replace,
+
+    /// Items of list from overlay are added after items of list from base
+    Append,
+
+    /// Items at the same position are merged, longer list defines length
+    ByIndex,
+}
+
+impl<'a> Object<'a> {
+    /// Writes `overlay` merged on top of `base` into given allocation
+    ///
+    /// Lists are merged with [`ListMerge::Replace`] strategy,
+    /// see [`merge_into_with`](Self::merge_into_with) for details
+    pub fn merge_into(
+        base: &Object<'_>,
+        overlay: &Object<'_>,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<Self> {
+        Self::merge_into_with(base, overlay, ListMerge::Replace, allocation)
+    }
+
+    /// Writes `overlay` merged on top of `base` into given allocation
+    ///
+    /// Items of `base` keep their order and items that exist only in `overlay`
+    /// are written after them. When both objects have item with the same key:
+    /// * nested objects and maps (of the same type) are merged recursively
+    /// * lists (of the same type) are merged with given strategy
+    /// * otherwise item from `overlay` is used
+    ///
+    /// ```
+    /// use binn_rs::{binn, List, ListMerge, Object, Value};
+    ///
+    /// let mut defaults_buf = [0; 64];
+    /// let defaults = binn!(defaults_buf.as_mut_slice(), {
+    ///     "interval": 10u16,
+    ///     "wifi": { "ssid": "default", "retries": 3u8 },
+    ///     "tags": ["base"],
+    /// })
+    /// .unwrap();
+    ///
+    /// let mut device_buf = [0; 64];
+    /// let device = binn!(device_buf.as_mut_slice(), {
+    ///     "wifi": { "ssid": "office" },
+    ///     "tags": ["device"],
+    /// })
+    /// .unwrap();
+    ///
+    /// let mut buf = [0; 128];
+    /// let config =
+    ///     Object::merge_into_with(&defaults, &device, ListMerge::Append, buf.as_mut_slice())
+    ///         .unwrap();
+    ///
+    /// let wifi: Object = config.get("wifi").unwrap().try_into().unwrap();
+    /// assert_eq!(wifi.get("ssid").unwrap(), Value::Text("office"));
+    /// assert_eq!(wifi.get("retries").unwrap(), Value::UInt8(3));
+    ///
+    /// let tags: List = config.get("tags").unwrap().try_into().unwrap();
+    /// assert_eq!(tags.count(), 2);
+    /// ```
+    pub fn merge_into_with(
+        base: &Object<'_>,
+        overlay: &Object<'_>,
+        lists: ListMerge,
+        allocation: impl Into<Allocation<'a>>,
+    ) -> Result<Self> {
+        let mut merged = Object::empty_mut_with_subtype(base.subtype(), allocation)?;
+        merge_keyed(&base.inner, &overlay.inner, &mut merged.inner, lists)?;
+
+        Ok(merged)
+    }
+}
+
+/// Writes merged items of objects or maps into `dst`
+fn merge_keyed(
+    base: &RawContainer<'_>,
+    overlay: &RawContainer<'_>,
+    dst: &mut RawContainer<'_>,
+    lists: ListMerge,
+) -> Result<()> {
+    for (key, base_value) in base.iter() {
+        match overlay.get(key) {
+            Some(overlay_value) => merge_values(key, base_value, overlay_value, dst, lists)?,
+            None => {
+                dst.add_value(key, base_value)?;
+            }
+        }
+    }
+
+    for (key, overlay_value) in overlay.iter() {
+        if base.get(key).is_none() {
+            dst.add_value(key, overlay_value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes merged items of lists into `dst`
+fn merge_lists(
+    base: &RawContainer<'_>,
+    overlay: &RawContainer<'_>,
+    dst: &mut RawContainer<'_>,
+    lists: ListMerge,
+) -> Result<()> {
+    let mut overlay_items = overlay.iter();
+
+    for (_, base_value) in base.iter() {
+        match overlay_items.next() {
+            Some((_, overlay_value)) if lists == ListMerge::ByIndex => {
+                merge_values(Key::Empty, base_value, overlay_value, dst, lists)?;
+            }
+            _ => {
+                dst.add_value(Key::Empty, base_value)?;
+            }
+        }
+    }
+
+    // for append strategy all overlay items are added after base ones
+    let overlay_items = match lists {
+        ListMerge::Append => overlay.iter(),
+        _ => overlay_items,
+    };
+    for (_, overlay_value) in overlay_items {
+        dst.add_value(Key::Empty, overlay_value)?;
+    }
+
+    Ok(())
+}
+
+/// Writes item that exists in both containers into `dst`
+fn merge_values(
+    key: Key<'_>,
+    base: Value<'_>,
+    overlay: Value<'_>,
+    dst: &mut RawContainer<'_>,
+    lists: ListMerge,
+) -> Result<()> {
+    let (base, overlay) = match (&base, &overlay) {
+        (Value::Map(base), Value::Map(overlay)) if base.subtype() == overlay.subtype() => {
+            (&base.inner, &overlay.inner)
+        }
+        (Value::Object(base), Value::Object(overlay)) if base.subtype() == overlay.subtype() => {
+            (&base.inner, &overlay.inner)
+        }
+        (Value::List(base), Value::List(overlay))
+            if base.subtype() == overlay.subtype() && lists != ListMerge::Replace =>
+        {
+            let mut child =
+                dst.add_empty_container(key, base.inner.data_type(), base.inner.key_type())?;
+            return merge_lists(&base.inner, &overlay.inner, &mut child, lists);
+        }
+        _ => {
+            dst.add_value(key, overlay)?;
+            return Ok(());
+        }
+    };
+
+    let mut child = dst.add_empty_container(key, base.data_type(), base.key_type())?;
+    merge_keyed(base, overlay, &mut child, lists)
+}
@@ -0,0 +1,337 @@
+//! Structural diff and patch of binn documents
+//!
+//! Patch is a list of operations, each operation is an object:
+//! * `op` - `"add"`, `"remove"` or `"replace"`
+//! * `path` - list of keys from root to changed item: text for keys of objects,
+//!   `i32` numbers for keys of maps and unsigned numbers for positions in lists
+//! * `value` - new value (only for `add` and `replace`)
+//!
+//! Operations are applied in order, so paths refer to the document with all
+//! previous operations applied. Items can only be added to the end of lists.
+use crate::data_type;
+use crate::error::{Error, Result};
+use crate::raw_container::{Key, KeyType, RawContainer};
+use crate::{Allocation, List, Object, Value};
+
+const ADD: &str = "add";
+const REMOVE: &str = "remove";
+const REPLACE: &str = "replace";
+
+/// Creates patch that transforms `old` document into `new` document
+///
+/// Patch is written into given allocation as list of operations.
+/// Objects and maps are compared key by key, lists are compared position
+/// by position. Other values (including containers of different types)
+/// are replaced if they are not equal:
+/// ```
+/// use binn_rs::{binn, diff, Value};
+///
+/// let mut old_buf = [0; 64];
+/// let old = binn!(old_buf.as_mut_slice(), {
+///     "name": "sensor",
+///     "interval": 10u16,
+///     "tags": ["a"],
+/// })
+/// .unwrap();
+///
+/// let mut new_buf = [0; 64];
+/// let new = binn!(new_buf.as_mut_slice(), {
+///     "name": "sensor",
+///     "interval": 60u16,
+///     "tags": ["a", "b"],
+/// })
+/// .unwrap();
+///
+/// let old = Value::deserialize(old.as_bytes()).unwrap();
+/// let new = Value::deserialize(new.as_bytes()).unwrap();
+///
+/// let mut patch_buf = [0; 128];
+/// let patch = diff(&old, &new, patch_buf.as_mut_slice()).unwrap();
+/// assert_eq!(patch.count(), 2);
+/// ```
+pub fn diff<'b>(
+    old: &Value<'_>,
+    new: &Value<'_>,
+    allocation: impl Into<Allocation<'b>>,
+) -> Result<List<'b>> {
+    let mut patch = List::empty_mut(allocation)?;
+    diff_values(old, new, &mut patch.inner, None)?;
+
+    Ok(patch)
+}
+
+/// Applies patch created by [`diff`] to given object in place
+///
+/// Object must be mutable, e.g. opened with [`Object::open_mut`]. Items keep
+/// their order, added items are written after existing ones.
+///
+/// Returns [`Error::Malformed`] if patch has invalid structure and
+/// [`Error::PatchConflict`] if some operation can't be applied. Structure of
+/// the whole patch is checked first, but operations applied before a conflict
+/// are not rolled back:
+/// ```
+/// use binn_rs::{apply_patch, binn, diff, Object, Value};
+///
+/// let mut old_buf = [0; 64];
+/// let old = binn!(old_buf.as_mut_slice(), { "interval": 10u16, "debug": true }).unwrap();
+/// let old_len = old.as_bytes().len();
+///
+/// let mut new_buf = [0; 64];
+/// let new = binn!(new_buf.as_mut_slice(), { "interval": 60u16 }).unwrap();
+///
+/// let old_value = Value::deserialize(old.as_bytes()).unwrap();
+/// let new_value = Value::deserialize(new.as_bytes()).unwrap();
+///
+/// let mut patch_buf = [0; 128];
+/// let patch = diff(&old_value, &new_value, patch_buf.as_mut_slice()).unwrap();
+///
+/// let mut patched_buf = [0; 64];
+/// patched_buf[..old_len].copy_from_slice(&old_buf[..old_len]);
+/// let mut patched = Object::open_mut(patched_buf.as_mut_slice()).unwrap();
+/// apply_patch(&mut patched, &patch).unwrap();
+/// assert_eq!(patched.as_bytes(), new.as_bytes());
+/// ```
+pub fn apply_patch(target: &mut Object<'_>, patch: &List<'_>) -> Result<()> {
+    for (_, op) in patch.inner.iter() {
+        Operation::parse(&op)?;
+    }
+
+    for (_, op) in patch.inner.iter() {
+        apply_operation(&mut target.inner, &Operation::parse(&op)?, 0)?;
+    }
+
+    Ok(())
+}
+
+/// Key of item inside its container
+#[derive(Clone, Copy)]
+enum Segment<'a> {
+    Key(Key<'a>),
+    Index(usize),
+}
+
+impl<'a> Segment<'a> {
+    fn to_value(self) -> Value<'a> {
+        match self {
+            Segment::Key(Key::Str(key)) => Value::Text(key),
+            Segment::Key(Key::Num(key)) => Value::Int32(key),
+            Segment::Key(Key::Empty) => unreachable!(),
+            Segment::Index(index) => Value::UInt32(index as u32),
+        }
+    }
+}
+
+/// Path from root to item, stored on the stack as linked list
+struct Path<'p> {
+    parent: Option<&'p Path<'p>>,
+    segment: Segment<'p>,
+}
+
+impl<'p> Path<'p> {
+    fn new(parent: Option<&'p Path<'p>>, segment: Segment<'p>) -> Self {
+        Self { parent, segment }
+    }
+}
+
+/// Writes segments of path into list starting from root
+fn write_path(path: Option<&Path<'_>>, list: &mut RawContainer<'_>) -> Result<()> {
+    if let Some(path) = path {
+        write_path(path.parent, list)?;
+        list.add_value(Key::Empty, path.segment.to_value())?;
+    }
+
+    Ok(())
+}
+
+fn add_operation(
+    patch: &mut RawContainer<'_>,
+    kind: &str,
+    path: Option<&Path<'_>>,
+    value: Option<Value<'_>>,
+) -> Result<()> {
+    let mut op = patch.add_empty_container(Key::Empty, data_type::OBJECT, KeyType::Str)?;
+    op.add_value(Key::Str("op"), Value::Text(kind))?;
+
+    let mut op_path = op.add_empty_container(Key::Str("path"), data_type::LIST, KeyType::Empty)?;
+    write_path(path, &mut op_path)?;
+
+    if let Some(value) = value {
+        op.add_value(Key::Str("value"), value)?;
+    }
+
+    Ok(())
+}
+
+fn diff_values(
+    old: &Value<'_>,
+    new: &Value<'_>,
+    patch: &mut RawContainer<'_>,
+    path: Option<&Path<'_>>,
+) -> Result<()> {
+    match (old, new) {
+        (Value::List(old), Value::List(new)) if old.subtype() == new.subtype() => {
+            diff_lists(&old.inner, &new.inner, patch, path)
+        }
+        (Value::Map(old), Value::Map(new)) if old.subtype() == new.subtype() => {
+            diff_keyed(&old.inner, &new.inner, patch, path)
+        }
+        (Value::Object(old), Value::Object(new)) if old.subtype() == new.subtype() => {
+            diff_keyed(&old.inner, &new.inner, patch, path)
+        }
+        (old, new) if old == new => Ok(()),
+        (_, new) => add_operation(patch, REPLACE, path, Some(new.borrowed())),
+    }
+}
+
+fn diff_keyed(
+    old: &RawContainer<'_>,
+    new: &RawContainer<'_>,
+    patch: &mut RawContainer<'_>,
+    path: Option<&Path<'_>>,
+) -> Result<()> {
+    for (key, old_value) in old.iter() {
+        let item_path = Path::new(path, Segment::Key(key));
+        match new.get(key) {
+            Some(new_value) => diff_values(&old_value, &new_value, patch, Some(&item_path))?,
+            None => add_operation(patch, REMOVE, Some(&item_path), None)?,
+        }
+    }
+
+    for (key, new_value) in new.iter() {
+        if old.get(key).is_none() {
+            let item_path = Path::new(path, Segment::Key(key));
+            add_operation(patch, ADD, Some(&item_path), Some(new_value))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn diff_lists(
+    old: &RawContainer<'_>,
+    new: &RawContainer<'_>,
+    patch: &mut RawContainer<'_>,
+    path: Option<&Path<'_>>,
+) -> Result<()> {
+    let mut new_items = new.iter();
+
+    for (index, (_, old_value)) in old.iter().enumerate() {
+        let Some((_, new_value)) = new_items.next() else {
+            break;
+        };
+        let item_path = Path::new(path, Segment::Index(index));
+        diff_values(&old_value, &new_value, patch, Some(&item_path))?;
+    }
+
+    // items are removed starting from the last one, so indexes of
+    // preceding items don't change
+    for index in (new.count()..old.count()).rev() {
+        let item_path = Path::new(path, Segment::Index(index));
+        add_operation(patch, REMOVE, Some(&item_path), None)?;
+    }
+
+    for (offset, (_, new_value)) in new_items.enumerate() {
+        let item_path = Path::new(path, Segment::Index(old.count() + offset));
+        add_operation(patch, ADD, Some(&item_path), Some(new_value))?;
+    }
+
+    Ok(())
+}
+
+/// Single operation of patch
+struct Operation<'a> {
+    kind: &'a str,
+    path: RawContainer<'a>,
+    value: Option<Value<'a>>,
+}
+
+impl<'a> Operation<'a> {
+    fn parse(value: &'a Value<'_>) -> Result<Self> {
+        let Value::Object(op) = value else {
+            return Err(Error::Malformed);
+        };
+
+        let kind = match op.get("op") {
+            Some(Value::Text(kind)) if [ADD, REMOVE, REPLACE].contains(&kind) => kind,
+            _ => return Err(Error::Malformed),
+        };
+        let path = match op.get("path") {
+            Some(Value::List(path)) => path.inner,
+            _ => return Err(Error::Malformed),
+        };
+        let value = op.get("value");
+        if value.is_none() != (kind == REMOVE) {
+            return Err(Error::Malformed);
+        }
+
+        Ok(Self { kind, path, value })
+    }
+}
+
+/// Applies operation to item of given container at `depth` of operation path
+fn apply_operation(
+    container: &mut RawContainer<'_>,
+    op: &Operation<'_>,
+    depth: usize,
+) -> Result<()> {
+    let Some(segment) = op.path.get_at(depth) else {
+        // only whole document can be replaced
+        let Some(Value::Object(obj)) = &op.value else {
+            return Err(Error::PatchConflict);
+        };
+        if op.kind != REPLACE {
+            return Err(Error::PatchConflict);
+        }
+
+        container.clear()?;
+        for (key, value) in obj.inner.iter() {
+            container.add_value(key, value)?;
+        }
+
+        return Ok(());
+    };
+
+    let (key, index) = find_item(container, &segment)?;
+
+    if depth + 1 < op.path.count() {
+        let mut child = index
+            .and_then(|index| container.container_at_mut(index))
+            .ok_or(Error::PatchConflict)?;
+
+        return apply_operation(&mut child, op, depth + 1);
+    }
+
+    match (op.kind, index, &op.value) {
+        (ADD, None, Some(value)) => container.add_value(key, value.borrowed()).map(|_| ()),
+        (REMOVE, Some(index), None) => container.remove_at(index),
+        (REPLACE, Some(index), Some(value)) => container.replace_at(index, value.borrowed()),
+        _ => Err(Error::PatchConflict),
+    }
+}
+
+/// Finds item of container that is referred by segment of path
+///
+/// Returns key of item and its index if item exists. Position after the last
+/// item of list is referred by empty key without index.
+fn find_item<'k>(
+    container: &RawContainer<'_>,
+    segment: &Value<'k>,
+) -> Result<(Key<'k>, Option<usize>)> {
+    let key = match (container.key_type(), segment) {
+        (KeyType::Str, Value::Text(key)) => Key::Str(key),
+        (KeyType::Num, key) => {
+            Key::Num(i32::try_from(key.borrowed()).map_err(|_| Error::PatchConflict)?)
+        }
+        (KeyType::Empty, index) => {
+            let count = container.count();
+            return match u32::try_from(index.borrowed()) {
+                Ok(index) if (index as usize) < count => Ok((Key::Empty, Some(index as usize))),
+                Ok(index) if index as usize == count => Ok((Key::Empty, None)),
+                _ => Err(Error::PatchConflict),
+            };
+        }
+        _ => return Err(Error::PatchConflict),
+    };
+
+    Ok((key, container.index_of(key)))
+}
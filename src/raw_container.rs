@@ -1,13 +1,16 @@
+use crate::data_type::{self, Type};
 use crate::error::Result;
-use crate::{utils, Error, List, Map, Object, Value};
+use crate::storage::Storage;
+use crate::{utils, Error, List, Map, Object, RawValue, UserContainer, Value};
 use byteorder::{BigEndian, ByteOrder};
-use core::marker::PhantomData;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
 
 use crate::size::Size;
 use crate::Allocation;
-use core::ptr::NonNull;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Key<'a> {
     Empty,
     Num(i32),
@@ -37,6 +40,15 @@ impl<'a> Key<'a> {
             _ => None,
         }
     }
+
+    /// Returns type of containers that use this key
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            Key::Empty => KeyType::Empty,
+            Key::Num(_) => KeyType::Num,
+            Key::Str(_) => KeyType::Str,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -44,29 +56,179 @@ pub enum KeyType {
     Empty,
     Num,
     Str,
+    /// Keys of user-defined containers are not known until container
+    /// is interpreted as list, map or object
+    Unknown,
 }
 
-/// Base internal type for all containers
+/// Bytes where container is stored
+#[derive(Debug)]
+enum Buffer<'a> {
+    /// Container is read only
+    Shared(&'a [u8]),
+    /// Whole allocation of root container
+    ///
+    /// Nested containers reborrow it from their parent, so only
+    /// the most nested one can be modified at a time
+    Unique(&'a mut [u8]),
+}
+
+/// Header of container ([type] [size] [count])
+#[derive(Clone, Copy, Debug)]
+struct Header {
+    count: Size,
+    data_type: Type,
+    len: Size,
+}
+
+impl Header {
+    /// Parses header of container at the beginning of given bytes
+    const fn parse(bytes: &[u8]) -> Result<Self> {
+        let data_type = tri!(Type::parse(bytes));
+        if !matches!(data_type.storage, Storage::Container) {
+            return Err(Error::Malformed);
+        }
+        let len = tri!(Size::parse(tri!(utils::tail(bytes, data_type.size()))));
+        let count = tri!(Size::parse(tri!(utils::tail(
+            bytes,
+            data_type.size() + len.size()
+        ))));
+
+        if len.value() > bytes.len() || len.value() < data_type.size() + len.size() + count.size() {
+            return Err(Error::Malformed);
+        }
+
+        Ok(Header {
+            count,
+            data_type,
+            len,
+        })
+    }
+
+    /// Reads header of container that is known to be valid
+    fn read(bytes: &[u8]) -> Self {
+        Self::parse(bytes).expect("container header is valid")
+    }
+
+    /// Returns size of this header in bytes
+    const fn size(&self) -> usize {
+        self.data_type.size() + self.len.size() + self.count.size()
+    }
+
+    /// Returns [size] and [count] of container after its data was resized
+    ///
+    /// Both use compact form whenever possible, [size] includes the header
+    /// with its new form
+    fn resized(&self, size_delta: isize, count_delta: isize) -> (Size, Size) {
+        // assume that we never create containers of size > 2GiB
+        let count = Size::new(self.count.value().checked_add_signed(count_delta).unwrap()).unwrap();
+        let data_len = self.len.value().checked_add_signed(size_delta).unwrap() - self.size();
+
+        let header = self.data_type.size() + count.size();
+        let len = match Size::new(header + 1 + data_len).unwrap() {
+            Size::Compact(len) => Size::Compact(len),
+            Size::Full(_) => Size::new(header + 4 + data_len).unwrap(),
+        };
+
+        (len, count)
+    }
+}
+
+/// Position of container inside of root container
 ///
-/// Clone is derived for convenience, should be used with caution
-#[derive(Clone, Debug, Eq)]
+/// Nested containers hold shared reference to position of their parent,
+/// so all parents can be updated when nested container is resized. Position is
+/// stored relative to data of parent, so it's still valid after header
+/// of some parent switched to full form and its data was shifted.
+#[derive(Debug)]
+struct Link<'a> {
+    /// Offset from the end of parent header
+    offset: usize,
+    parent: Option<&'a Link<'a>>,
+}
+
+impl<'a> Link<'a> {
+    const ROOT: Link<'static> = Link {
+        offset: 0,
+        parent: None,
+    };
+
+    /// Returns position of container in given buffer
+    fn position(&self, buf: &[u8]) -> usize {
+        match self.parent {
+            Some(parent) => {
+                let start = parent.position(buf);
+                start + Header::read(&buf[start..]).size() + self.offset
+            }
+            None => self.offset,
+        }
+    }
+
+    /// Updates header of container and all its parents after its data was resized
+    ///
+    /// Data of container must already be moved, so all used bytes of the root
+    /// container end at its old length plus `size_delta`. Data is shifted again
+    /// if size or count switched between compact and full form
+    fn resize(&self, buf: &mut [u8], size_delta: isize, count_delta: isize) {
+        // headers of parents are not updated yet
+        let end = Header::read(buf)
+            .len
+            .value()
+            .checked_add_signed(size_delta)
+            .unwrap();
+        let position = self.position(buf);
+        let header = Header::read(&buf[position..]);
+        let (len, count) = header.resized(size_delta, count_delta);
+        let shift = (len.size() + count.size()) as isize
+            - (header.len.size() + header.count.size()) as isize;
+
+        // shift all data if len or count switched between compact and full form
+        if shift != 0 {
+            let data_start = position + header.size();
+            buf.copy_within(
+                data_start..end,
+                data_start.checked_add_signed(shift).unwrap(),
+            );
+        }
+
+        // size of buffer is already checked
+        let buf_header = &mut buf[position + header.data_type.size()..];
+        let buf_header = len.write(buf_header).unwrap();
+        count.write(buf_header).unwrap();
+
+        if let Some(parent) = self.parent {
+            parent.resize(buf, size_delta + shift, 0);
+        }
+    }
+}
+
+/// Base internal type for all containers
 pub struct RawContainer<'a> {
-    buf: NonNull<[u8]>,
-    count: Size,
+    buf: Buffer<'a>,
+    compact_ints: bool,
+    data_type: Type,
     key_type: KeyType,
-    len: Size,
-    mutable: bool,
-    parent: Option<NonNull<RawContainer<'a>>>,
+    link: Link<'a>,
+}
 
-    _marker: PhantomData<&'a [u8]>,
+impl<'a> fmt::Debug for RawContainer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawContainer")
+            .field("data_type", &self.data_type)
+            .field("key_type", &self.key_type)
+            .field("bytes", &self.as_bytes())
+            .finish()
+    }
 }
 
 impl<'a> PartialEq for RawContainer<'a> {
     fn eq(&self, other: &Self) -> bool {
-        self.count == other.count && self.as_bytes() == other.as_bytes()
+        self.as_bytes() == other.as_bytes()
     }
 }
 
+impl<'a> Eq for RawContainer<'a> {}
+
 impl<'a> RawContainer<'a> {
     /// Adds new container field
     ///
@@ -76,33 +238,61 @@ impl<'a> RawContainer<'a> {
         key: Key<'_>,
         container: &RawContainer<'_>,
     ) -> Result<RawContainer<'_>> {
-        self.check_available_size(key.size() + container.len.value())?;
-
-        let key_size = self.ensure_mutable()?.write_key(key)?;
-
-        let parent = NonNull::new(self as *mut RawContainer<'_>);
-
-        // write key doesn't update container size, so insert position
-        // will be at the beginning of the key
-        let buf = &mut self.insert_position()[key_size..];
-
-        // size is already checked, no error possible
-        buf[..container.len.value()].copy_from_slice(container.as_bytes());
+        let len = container.as_bytes().len();
+        self.add_raw(key, container.as_bytes())?;
+
+        // new container is the last item of this container
+        let header = self.header();
+        let link = Link {
+            offset: header.len.value() - header.size() - len,
+            parent: Some(&self.link),
+        };
 
-        // create new container, that will point inside our buffer
-        let inner = RawContainer {
-            buf: buf.into(),
-            count: container.count,
-            key_type: self.key_type,
-            len: container.len,
-            mutable: true,
-            parent,
-            _marker: PhantomData,
+        // create new container, that reborrows our buffer
+        let Buffer::Unique(buf) = &mut self.buf else {
+            unreachable!("container is checked to be mutable");
         };
 
-        self.increment_size_and_count(key_size + container.len.value(), 1);
+        Ok(RawContainer {
+            buf: Buffer::Unique(buf),
+            compact_ints: self.compact_ints,
+            data_type: container.data_type,
+            key_type: container.nested_key_type(),
+            link,
+        })
+    }
+
+    /// Adds new field with given key and serialized value
+    ///
+    /// Value is copied as is, so it must be valid
+    pub fn add_raw(&mut self, key: Key<'_>, raw: &[u8]) -> Result<()> {
+        check_key(key)?;
+
+        let len = self.header().len.value();
+        self.splice(len..len, key.size() + raw.len(), 1, |buf| {
+            let key_size = write_key(key, buf);
+            buf[key_size..].copy_from_slice(raw);
+        })
+    }
 
-        Ok(inner)
+    /// Adds new empty container of given type
+    ///
+    /// Returns mutable container inside this container, so it can be modified
+    pub fn add_empty_container(
+        &mut self,
+        key: Key<'_>,
+        data_type: Type,
+        key_type: KeyType,
+    ) -> Result<RawContainer<'_>> {
+        // header consists of [type], [size] and [count]
+        let mut header = [0; 4];
+        let len = data_type.size() + 2;
+        let buf = data_type.write(&mut header);
+        let buf = Size::new(len).unwrap().write(buf)?;
+        Size::new(0).unwrap().write(buf)?;
+
+        let empty = RawContainer::from_bytes(&header[..len], key_type)?;
+        self.add_container(key, &empty)
     }
 
     /// Adds new field with given name and value
@@ -125,29 +315,36 @@ impl<'a> RawContainer<'a> {
                 let inner = self.add_container(key, &obj.inner)?;
                 return Ok(Value::Object(Object { inner }));
             }
+            Value::UserContainer(container) => {
+                let inner = self.add_container(key, &container.inner)?;
+                return Ok(Value::UserContainer(UserContainer { inner }));
+            }
             _ => {}
         }
 
-        let data_size = value.total_size();
-        self.check_available_size(key.size() + data_size)?;
-
-        let key_size = self.ensure_mutable()?.write_key(key)?;
-
-        // write key doesn't update container size, so len
-        // will be at the beginning of the key
-        let len_with_key = self.len.value() + key_size;
+        let value = match self.compact_ints {
+            true => value.to_smallest_int().unwrap_or(value),
+            false => value,
+        };
 
-        let len_with_key = len_with_key + self.increment_size_and_count(key_size + data_size, 1);
+        let data_size = value.total_size();
+        check_key(key)?;
 
-        let buf = &mut self.as_bytes_mut()[len_with_key..];
+        let len = self.header().len.value();
+        self.splice(len..len, key.size() + data_size, 1, |buf| {
+            let key_size = write_key(key, buf);
+            // size is already checked, no error possible
+            value.write(&mut buf[key_size..]).unwrap();
+        })?;
 
-        // size is already checked, no error possible
-        value.write(buf).unwrap();
+        // value is the last item of this container
+        let len = self.header().len.value();
+        let buf = &mut self.as_bytes_mut()[len - data_size..];
 
         // skip size and type entry, used for restoring of text and blob
         let buf = &buf[value.get_type().size()..];
-        let buf = if let Some(size) = value.data_size() {
-            &buf[Size::new(size).unwrap().size()..]
+        let buf = if let Some(size) = value.size_entry() {
+            &buf[size.size()..]
         } else {
             // buf later is used only for arbitrary sized values (text and blob)
             &[]
@@ -189,6 +386,8 @@ impl<'a> RawContainer<'a> {
                 Value::DecimalStr(core::str::from_utf8(text).unwrap())
             }
             Value::Blob(bytes) => Value::Blob(&buf[..bytes.len()]),
+            Value::UInt128(v) => Value::UInt128(v),
+            Value::Int128(v) => Value::Int128(v),
             Value::Empty(sub) => Value::Empty(sub),
             Value::Byte(sub, v) => Value::Byte(sub, v),
             Value::Word(sub, v) => Value::Word(sub, v),
@@ -208,37 +407,73 @@ impl<'a> RawContainer<'a> {
     /// Returns slice of bytes representing current container
     /// Only actually used bytes are included
     pub fn as_bytes(&self) -> &[u8] {
-        unsafe {
-            //SAFETY: buf is valid for lifetime of ContainerState
-            &self.buf.as_ref()[..self.len.value()]
-        }
+        let buf = self.buf();
+        let position = self.link.position(buf);
+        let len = Header::read(&buf[position..]).len.value();
+
+        &buf[position..position + len]
     }
 
     pub fn count(&self) -> usize {
-        self.count.value()
+        self.header().count.value()
+    }
+
+    /// Returns type of this container
+    pub const fn data_type(&self) -> Type {
+        self.data_type
+    }
+
+    pub fn compact_ints(&self) -> bool {
+        self.compact_ints
+    }
+
+    pub fn set_compact_ints(&mut self, compact: bool) {
+        self.compact_ints = compact;
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
     }
 
     /// Create read-only container from given slice
+    ///
+    /// Items of container with unknown key type are not checked
     pub fn from_bytes(bytes: &[u8], key_type: KeyType) -> Result<RawContainer<'_>> {
-        // skip type byte
-        let len: Size = bytes[1..].try_into()?;
-        let count: Size = bytes[(len.size() + 1)..].try_into()?;
+        RawContainer::new(Buffer::Shared(bytes), key_type)
+    }
 
-        let container = RawContainer {
-            buf: bytes.into(),
-            count,
-            key_type,
-            len,
-            mutable: false,
-            parent: None,
-            _marker: PhantomData,
+    /// Create read-only container from static bytes in const context
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in const context) if bytes don't
+    /// start with valid container
+    pub const fn from_static(bytes: &'static [u8], key_type: KeyType) -> RawContainer<'static> {
+        let header = match Header::parse(bytes) {
+            Ok(header) => header,
+            Err(_) => panic!("container is malformed"),
         };
+        if validate(bytes, key_type).is_err() {
+            panic!("container is malformed");
+        }
 
-        // check that all items in container can be parsed when iterated
-        if container.iter().count() == count.value() {
-            Ok(container)
-        } else {
-            Err(Error::Malformed)
+        RawContainer {
+            buf: Buffer::Shared(bytes),
+            compact_ints: false,
+            data_type: header.data_type,
+            key_type,
+            link: Link::ROOT,
+        }
+    }
+
+    /// Create read-only container from bytes that are already validated
+    pub fn from_valid_bytes(bytes: &[u8], key_type: KeyType) -> RawContainer<'_> {
+        RawContainer {
+            buf: Buffer::Shared(bytes),
+            compact_ints: false,
+            data_type: Header::read(bytes).data_type,
+            key_type,
+            link: Link::ROOT,
         }
     }
 
@@ -246,220 +481,683 @@ impl<'a> RawContainer<'a> {
     ///
     /// Allocation must contain valid container data
     pub fn new_mut(allocation: Allocation<'_>, key_type: KeyType) -> Result<RawContainer<'_>> {
-        let container = match allocation {
-            Allocation::Static(bytes) => Self::from_bytes(bytes, key_type)?,
-        };
+        match allocation {
+            Allocation::Static(bytes) => RawContainer::new(Buffer::Unique(bytes), key_type),
+        }
+    }
 
-        // we have mutable pointer for storage
-        Ok(RawContainer {
-            mutable: true,
-            ..container
-        })
+    /// Create writable container from allocation that already contains
+    /// validated container data
+    #[cfg(feature = "ffi")]
+    pub fn new_mut_valid(allocation: Allocation<'_>, key_type: KeyType) -> RawContainer<'_> {
+        match allocation {
+            Allocation::Static(bytes) => RawContainer {
+                data_type: Header::read(bytes).data_type,
+                buf: Buffer::Unique(bytes),
+                compact_ints: false,
+                key_type,
+                link: Link::ROOT,
+            },
+        }
     }
 
     pub fn get(&self, key: Key<'_>) -> Option<Value<'_>> {
-        self.iter()
-            .find(|(item_key, _)| item_key == &key)
-            .map(|(_, v)| v)
+        self.get_raw(key).map(RawValue::decode)
     }
 
     pub fn get_at(&self, pos: usize) -> Option<Value<'_>> {
         self.iter().nth(pos).map(|(_, v)| v)
     }
 
+    pub fn get_raw(&self, key: Key<'_>) -> Option<&RawValue> {
+        self.iter()
+            .raw()
+            .find(|(item_key, _)| item_key == &key)
+            .map(|(_, raw)| RawValue::new_unchecked(raw))
+    }
+
+    pub fn get_raw_at(&self, pos: usize) -> Option<&RawValue> {
+        self.iter()
+            .raw()
+            .nth(pos)
+            .map(|(_, raw)| RawValue::new_unchecked(raw))
+    }
+
+    /// Returns index of item with given key
+    pub fn index_of(&self, key: Key<'_>) -> Option<usize> {
+        self.iter().keys().position(|item_key| item_key == key)
+    }
+
+    /// Removes item at given index
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no item at given index
+    pub fn remove_at(&mut self, index: usize) -> Result<()> {
+        let (key, offset, raw) = self.item_at(index);
+        let range = offset - key.size()..offset + raw.len();
+
+        self.splice(range, 0, -1, |_| {})
+    }
+
+    /// Replaces value of item at given index, key of item is kept
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no item at given index
+    pub fn replace_at(&mut self, index: usize, value: Value<'_>) -> Result<()> {
+        let (_, offset, raw) = self.item_at(index);
+        let range = offset..offset + raw.len();
+
+        let container = match &value {
+            Value::List(list) => &list.inner,
+            Value::Map(map) => &map.inner,
+            Value::Object(obj) => &obj.inner,
+            Value::UserContainer(container) => &container.inner,
+            _ => {
+                let value = match self.compact_ints {
+                    true => value.to_smallest_int().unwrap_or(value),
+                    false => value,
+                };
+                return self.splice(range, value.total_size(), 0, |buf| {
+                    // size is already checked, no error possible
+                    value.write(buf).unwrap();
+                });
+            }
+        };
+
+        let bytes = container.as_bytes();
+        self.splice(range, bytes.len(), 0, |buf| buf.copy_from_slice(bytes))
+    }
+
+    /// Removes all items of this container
+    pub fn clear(&mut self) -> Result<()> {
+        let header = self.header();
+        let count = header.count.value() as isize;
+
+        self.splice(header.size()..header.len.value(), 0, -count, |_| {})
+    }
+
+    /// Returns container stored at given index, so it can be modified
+    ///
+    /// Returns `None` if there is no item at given index or it's not a container
+    pub fn container_at_mut(&mut self, index: usize) -> Option<RawContainer<'_>> {
+        let (_, offset, raw) = self.iter().raw_items().nth(index)?;
+        let data_type = Header::parse(raw).ok()?.data_type;
+        let link = Link {
+            offset: offset - self.header().size(),
+            parent: Some(&self.link),
+        };
+
+        let Buffer::Unique(buf) = &mut self.buf else {
+            return None;
+        };
+
+        Some(RawContainer {
+            buf: Buffer::Unique(buf),
+            compact_ints: self.compact_ints,
+            data_type,
+            key_type: match data_type {
+                data_type::LIST => KeyType::Empty,
+                data_type::MAP => KeyType::Num,
+                data_type::OBJECT => KeyType::Str,
+                _ => KeyType::Unknown,
+            },
+            link,
+        })
+    }
+
     pub fn iter(&self) -> RawIterator<'_> {
-        RawIterator {
-            container: self,
-            cursor: 1 + self.len.size() + self.count.size(), // skip header of container
+        RawIterator::new(self.as_bytes(), self.key_type)
+    }
+
+    pub fn iter_at(&self, position: Position) -> Option<RawIterator<'_>> {
+        RawIterator::new_at(self.as_bytes(), self.key_type, position)
+    }
+
+    /// Returns read-only container that uses the same data
+    pub fn read_only(&self) -> RawContainer<'_> {
+        RawContainer {
+            buf: Buffer::Shared(self.as_bytes()),
+            compact_ints: self.compact_ints,
+            data_type: self.data_type,
             key_type: self.key_type,
+            link: Link::ROOT,
         }
     }
 
-    /// Returns slice of bytes representing current container
+    /// Interprets this container as container with given key type
+    ///
+    /// Returns error if items can't be parsed with this key type
+    pub fn with_key_type(self, key_type: KeyType) -> Result<Self> {
+        let container = RawContainer { key_type, ..self };
+        container.validate()?;
+
+        Ok(container)
+    }
+
+    /// Creates container which starts at the beginning of given buffer
+    fn new(buf: Buffer<'_>, key_type: KeyType) -> Result<RawContainer<'_>> {
+        let header = match &buf {
+            Buffer::Shared(bytes) => Header::parse(bytes)?,
+            Buffer::Unique(bytes) => Header::parse(bytes)?,
+        };
+
+        let container = RawContainer {
+            buf,
+            compact_ints: false,
+            data_type: header.data_type,
+            key_type,
+            link: Link::ROOT,
+        };
+
+        container.validate()?;
+
+        Ok(container)
+    }
+
+    /// Returns key type of this container when it's added into other container
+    ///
+    /// Key type of user-defined container can't be derived from its type,
+    /// so the one it was created with is kept
+    fn nested_key_type(&self) -> KeyType {
+        match self.data_type {
+            data_type::LIST => KeyType::Empty,
+            data_type::MAP => KeyType::Num,
+            data_type::OBJECT => KeyType::Str,
+            _ => self.key_type,
+        }
+    }
+
+    /// Returns whole buffer where this container is stored
+    fn buf(&self) -> &[u8] {
+        match &self.buf {
+            Buffer::Shared(buf) => buf,
+            Buffer::Unique(buf) => buf,
+        }
+    }
+
+    /// Returns current header of this container
+    fn header(&self) -> Header {
+        let buf = self.buf();
+        Header::read(&buf[self.link.position(buf)..])
+    }
+
+    /// Returns slice of bytes starting at current container
+    /// and ending at the end of allocation
     ///
     /// # Panics:
     ///
     /// Panics if this container is readonly
     fn as_bytes_mut(&mut self) -> &mut [u8] {
-        self.ensure_mutable().expect("container is readonly");
-        unsafe {
-            //SAFETY: buf is valid for lifetime of ContainerState
-            // if readonly is false, than this buffer was created from mutable slice
-            self.buf.as_mut()
+        match &mut self.buf {
+            Buffer::Shared(_) => panic!("container is readonly"),
+            Buffer::Unique(buf) => {
+                let position = self.link.position(buf);
+                &mut buf[position..]
+            }
         }
     }
-    /// Check if new item with given size can be added
-    /// Size must include key size
-    fn check_available_size(&mut self, item_size: usize) -> Result<()> {
-        let len = self.len.value();
-        let mut new_len = len + item_size;
-        // adjust len if len or count can't be compacted anymore
-        if self.len.is_u8() && !Size::is_compactable(new_len) {
-            new_len += 3;
-        }
-        if self.count.is_u8() && !Size::is_compactable(self.count.value() + 1) {
-            new_len += 3;
+
+    /// Check if data of this container can be resized by given number of bytes
+    ///
+    /// Headers of this container and its parents might switch
+    /// to full form, so extra space for them is required too
+    fn check_available_size(&self, size_delta: isize, count_delta: isize) -> Result<()> {
+        let buf = self.buf();
+
+        let mut growth = size_delta;
+        let mut count_delta = count_delta;
+        let mut link = Some(&self.link);
+        while let Some(current) = link {
+            let header = Header::read(&buf[current.position(buf)..]);
+            let (len, _) = header.resized(growth, count_delta);
+            growth = len.value() as isize - header.len.value() as isize;
+            count_delta = 0;
+            link = current.parent;
         }
 
-        let buf = self.insert_position();
+        // all used bytes belong to the root container, space after it is free
+        let available = buf.len() - Header::read(buf).len.value();
 
-        if buf.len() < new_len - len {
-            Err(Error::SmallBuffer(new_len - len - buf.len()))
-        } else {
-            Ok(())
+        match usize::try_from(growth) {
+            Ok(required) if required > available => Err(Error::SmallBuffer(required - available)),
+            _ => Ok(()),
         }
     }
 
+    /// Checks that all items in container can be parsed when iterated
+    ///
+    /// Items of container with unknown key type are not checked
+    fn validate(&self) -> Result<()> {
+        validate(self.as_bytes(), self.key_type)
+    }
+
     /// Checks that container, otherwise returns error
     fn ensure_mutable(&mut self) -> Result<&mut Self> {
-        if self.mutable {
-            Ok(self)
-        } else {
-            Err(Error::ReadOnly)
+        match self.buf {
+            Buffer::Shared(_) => Err(Error::ReadOnly),
+            Buffer::Unique(_) => Ok(self),
         }
     }
 
-    /// Updates parent of this container
+    /// Replaces bytes of this container in given range with `len` bytes
+    /// written by `write` and updates count of items
     ///
-    /// Returns size of data shift in case size or count turned from compact to full form
-    fn increment_size_and_count(&mut self, extra_size: usize, extra_count: usize) -> usize {
-        let new_count = Size::new(self.count.value() + extra_count).unwrap();
+    /// Range is relative to the start of container. Data after it (including
+    /// items of parents) is moved, so containers can be modified anywhere
+    /// inside of the root container
+    fn splice(
+        &mut self,
+        range: Range<usize>,
+        len: usize,
+        count_delta: isize,
+        write: impl FnOnce(&mut [u8]),
+    ) -> Result<()> {
+        let size_delta = len as isize - range.len() as isize;
+        self.ensure_mutable()?
+            .check_available_size(size_delta, count_delta)?;
+
+        let Buffer::Unique(buf) = &mut self.buf else {
+            unreachable!("container is checked to be mutable");
+        };
 
-        let mut data_start = 3;
-        if !self.len.is_u8() {
-            data_start += 3
-        }
-        if !self.count.is_u8() {
-            data_start += 3
-        }
+        let position = self.link.position(buf);
+        let end = Header::read(buf).len.value();
+        let (start, range_end) = (position + range.start, position + range.end);
+        buf.copy_within(range_end..end, start + len);
+        write(&mut buf[start..start + len]);
 
-        let mut shift = 0;
-        if self.count.is_u8() && !new_count.is_u8() {
-            shift += 3;
+        self.link.resize(buf, size_delta, count_delta);
+
+        Ok(())
+    }
+
+    /// Returns item at given index
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no item at given index
+    fn item_at(&self, index: usize) -> RawItem<'_> {
+        self.iter()
+            .raw_items()
+            .nth(index)
+            .expect("item exists at given index")
+    }
+}
+
+/// Checks that given key can be written
+fn check_key(key: Key<'_>) -> Result<()> {
+    match key {
+        Key::Str(key) if key.len() > 255 => Err(Error::LongKey),
+        _ => Ok(()),
+    }
+}
+
+/// Writes key at the beginning of given buffer and returns how many bytes were written
+///
+/// Buffer must be big enough and key must be checked with [`check_key`]
+fn write_key(key: Key<'_>, buf: &mut [u8]) -> usize {
+    match key {
+        Key::Empty => {}
+        Key::Num(key) => BigEndian::write_i32(buf, key),
+        Key::Str(key) => {
+            // key does not include null terminator, byte with its length
+            // is not included too
+            buf[0] = key.len() as u8;
+            buf[1..=key.len()].copy_from_slice(key.as_bytes());
         }
+    }
 
-        // assume that we never create containers of size > 2GiB
-        let new_len = Size::new(self.len.value() + extra_size + shift).unwrap();
-        if self.len.is_u8() && !new_len.is_u8() {
-            shift += 3;
+    key.size()
+}
+
+/// Position of item inside of container
+///
+/// Position can be stored and used later to resume iteration over the same
+/// container without going through preceding items again. It stays valid
+/// when new items are added to container, but not after items are removed
+/// or replaced
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Position {
+    /// Offset of item from the start of container data (after header)
+    offset: usize,
+    /// Index of item in container
+    index: usize,
+}
+
+/// Iterator over items of serialized container
+#[derive(Clone, Debug)]
+pub struct RawIterator<'a> {
+    bytes: &'a [u8],
+    key_type: KeyType,
+    /// Offset of container data (size of header)
+    data: usize,
+    /// Offset of next item from the front
+    front: usize,
+    /// Index of next item from the front
+    index: usize,
+    /// How many items were not returned from either end yet
+    remaining: usize,
+    /// Offsets of items not returned from the back yet, built on first
+    /// iteration from the back
+    #[cfg(feature = "alloc")]
+    offsets: Option<alloc::vec::Vec<usize>>,
+}
+
+impl<'a> RawIterator<'a> {
+    /// Creates iterator over items of container serialized in given bytes
+    ///
+    /// Bytes must start with valid container
+    pub fn new(bytes: &'a [u8], key_type: KeyType) -> Self {
+        let start = Position {
+            offset: 0,
+            index: 0,
+        };
+
+        Self::with_header(bytes, key_type, Header::read(bytes), start)
+    }
+
+    /// Creates iterator that starts at given position
+    ///
+    /// Returns `None` if position doesn't point to item of this container.
+    /// Position may come from other container or be stale, so items after
+    /// it are checked to be exactly the rest of container
+    pub fn new_at(bytes: &'a [u8], key_type: KeyType, position: Position) -> Option<Self> {
+        let header = Header::read(bytes);
+        let len = header.len.value();
+        let count = header.count.value();
+        if position.offset > len - header.size() || position.index > count {
+            return None;
         }
-        let new_len = Size::new(self.len.value() + extra_size + shift).unwrap();
-        // shift all data if len or count switched from compact form to full form
 
-        if shift > 0 {
-            let data_end = self.as_bytes_mut().len() - shift;
-            self.as_bytes_mut()
-                .copy_within(data_start..data_end, data_start + shift);
+        let offset = header.size() + position.offset;
+        let remaining = count - position.index;
+        if !matches!(key_type, KeyType::Unknown) {
+            validate_items(&bytes[..len], key_type, offset, remaining).ok()?;
         }
 
-        // create len again since shift might change
-        self.len = new_len;
-        self.count = new_count;
+        Some(Self::with_header(bytes, key_type, header, position))
+    }
 
-        // size of buffer is already checked
-        let buf = self.as_bytes_mut();
-        let buf = new_len.write(&mut buf[1..]).unwrap();
-        new_count.write(buf).unwrap();
+    fn with_header(bytes: &'a [u8], key_type: KeyType, header: Header, start: Position) -> Self {
+        let count = match key_type {
+            KeyType::Unknown => 0,
+            _ => header.count.value(),
+        };
+
+        RawIterator {
+            bytes: &bytes[..header.len.value()],
+            key_type,
+            data: header.size(),
+            front: header.size() + start.offset,
+            index: start.index,
+            remaining: count - start.index,
+            #[cfg(feature = "alloc")]
+            offsets: None,
+        }
+    }
 
-        if let Some(parent) = self.parent_mut() {
-            parent.increment_size_and_count(extra_size + shift, 0);
+    /// Returns position of next item from the front
+    pub fn current_position(&self) -> Position {
+        Position {
+            offset: self.front - self.data,
+            index: self.index,
         }
+    }
 
-        shift
+    /// Iterates over keys and serialized values of items
+    pub fn raw(self) -> impl Iterator<Item = (Key<'a>, &'a [u8])> {
+        self.raw_items().map(|(key, _, raw)| (key, raw))
     }
 
-    /// Returns place where new items should be added
-    fn insert_position(&mut self) -> &mut [u8] {
-        let len = self.len.value();
-        &mut self.as_bytes_mut()[len..]
+    /// Iterates over keys of items without decoding values
+    pub fn keys(self) -> impl DoubleEndedIterator<Item = Key<'a>> + ExactSizeIterator {
+        self.raw_items().map(|(key, _, _)| key)
     }
 
-    /// Returns parent container of this container
-    fn parent_mut(&mut self) -> Option<&mut RawContainer<'a>> {
-        if let Some(mut parent) = self.parent {
-            // SAFETY: parent must be valid since lifetime of this document
-            // is tied to lifetime of parent and it's buffer
-            unsafe { Some(parent.as_mut()) }
-        } else {
-            None
-        }
+    /// Iterates over items with offsets and serialized values
+    pub fn raw_items(self) -> RawItems<'a> {
+        RawItems(self)
     }
 
-    /// Begins new item (writes its key) and returns how many bytes were written
-    ///
-    /// Container count and size is not updated
-    fn write_key(&mut self, key: Key<'_>) -> Result<usize> {
-        match key {
-            Key::Empty => Ok(0),
-            Key::Num(key) => self.write_i32_key(key),
-            Key::Str(key) => self.write_str_key(key),
+    /// Returns next item from the front
+    fn next_item(&mut self) -> Option<RawItem<'a>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        match read_item(self.bytes, self.key_type, self.front) {
+            Some((size, item)) => {
+                self.front += size;
+                self.index += 1;
+                self.remaining -= 1;
+                Some(item)
+            }
+            None => {
+                // stop iteration if container is malformed
+                self.remaining = 0;
+                None
+            }
         }
     }
 
-    /// Writes str key and returns how many bytes were written
-    ///
-    /// Size and count are not updated
-    fn write_str_key(&mut self, key: &str) -> Result<usize> {
-        let key = key.as_bytes();
+    /// Returns next item from the back
+    fn next_back_item(&mut self) -> Option<RawItem<'a>> {
+        if self.remaining == 0 {
+            return None;
+        }
 
-        if key.len() > 255 {
-            return Err(Error::LongKey);
+        let (key_type, bytes) = (self.key_type, self.bytes);
+        let last = self
+            .last_offset()
+            .and_then(|offset| Some(read_item(bytes, key_type, offset)?.1));
+
+        match last {
+            Some(item) => {
+                self.remaining -= 1;
+                Some(item)
+            }
+            None => {
+                self.remaining = 0;
+                None
+            }
         }
+    }
 
-        // key does not include null terminator but includes key length (1 byte)
-        let key_size = key.len() + 1;
+    /// Returns offsets of items that were not returned yet
+    fn offsets(&self) -> impl Iterator<Item = usize> + 'a {
+        let (bytes, key_type) = (self.bytes, self.key_type);
+        let mut offset = self.front;
 
-        // size of buffer is already checked
-        let buf = self.insert_position();
+        (0..self.remaining).map_while(move |_| {
+            let (size, _) = read_item(bytes, key_type, offset)?;
+            offset += size;
+            Some(offset - size)
+        })
+    }
 
-        // do not include byte with size
-        buf[0] = key_size as u8 - 1;
-        buf[1..key_size].copy_from_slice(key);
+    /// Returns offset of last item that was not returned yet
+    #[cfg(feature = "alloc")]
+    fn last_offset(&mut self) -> Option<usize> {
+        if self.offsets.is_none() {
+            self.offsets = Some(self.offsets().collect());
+        }
 
-        Ok(key_size)
+        self.offsets.as_mut()?.pop()
     }
 
-    /// Writes i32 key and returns how many bytes were written
+    /// Returns offset of last item that was not returned yet
     ///
-    /// Size and count are not updated
-    fn write_i32_key(&mut self, key: i32) -> Result<usize> {
-        // size of buffer is already checked
-        BigEndian::write_i32(self.insert_position(), key);
+    /// Without allocations all items before it have to be visited again
+    #[cfg(not(feature = "alloc"))]
+    fn last_offset(&mut self) -> Option<usize> {
+        self.offsets().last()
+    }
+}
 
-        Ok(4)
+/// Key, offset of serialized value from the start of container and
+/// serialized value of container item
+pub type RawItem<'a> = (Key<'a>, usize, &'a [u8]);
+
+/// Checks that all items of container at the beginning of given bytes
+/// can be parsed, nested containers are checked as well
+///
+/// Items of container with unknown key type are not checked
+const fn validate(bytes: &[u8], key_type: KeyType) -> Result<()> {
+    let header = tri!(Header::parse(bytes));
+    if matches!(key_type, KeyType::Unknown) {
+        return Ok(());
     }
+
+    let bytes = bytes.split_at(header.len.value()).0;
+    validate_items(bytes, key_type, header.size(), header.count.value())
 }
 
-pub struct RawIterator<'a> {
-    container: &'a RawContainer<'a>,
-    cursor: usize,
+/// Checks that given number of items starting at offset can be parsed and
+/// that they end exactly at the end of container
+///
+/// Bytes must be limited to container length
+const fn validate_items(
+    bytes: &[u8],
     key_type: KeyType,
+    offset: usize,
+    count: usize,
+) -> Result<()> {
+    let mut offset = offset;
+    let mut index = 0;
+    while index < count {
+        let buf = tri!(utils::tail(bytes, offset));
+        let key_size = match key_type {
+            KeyType::Empty => 0,
+            KeyType::Num => tri!(utils::head(buf, 4)).len(),
+            KeyType::Str => tri!(utils::read_key(buf)).len() + 1,
+            KeyType::Unknown => unreachable!(),
+        };
+        let buf = tri!(utils::tail(buf, key_size));
+        let len = tri!(utils::read_value_len(buf));
+        tri!(validate_value(buf.split_at(len).0));
+
+        offset += key_size + len;
+        index += 1;
+    }
+
+    if offset == bytes.len() {
+        Ok(())
+    } else {
+        Err(Error::Malformed)
+    }
+}
+
+/// Checks that serialized value can be deserialized
+///
+/// Length of value must be already checked
+const fn validate_value(bytes: &[u8]) -> Result<()> {
+    let data_type = tri!(Type::parse(bytes));
+    let value = bytes.split_at(data_type.size()).1;
+
+    match data_type {
+        data_type::LIST => validate(bytes, KeyType::Empty),
+        data_type::MAP => validate(bytes, KeyType::Num),
+        data_type::OBJECT => validate(bytes, KeyType::Str),
+        Type {
+            storage: Storage::Container,
+            subtype: _,
+        } => validate(bytes, KeyType::Unknown),
+        Type {
+            storage: Storage::String,
+            subtype: _,
+        } => match utils::read_text(value) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(err),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Reads item at given offset and returns its size with key
+fn read_item(bytes: &[u8], key_type: KeyType, offset: usize) -> Option<(usize, RawItem<'_>)> {
+    let buf = bytes.get(offset..)?;
+    let key = match key_type {
+        KeyType::Unknown => return None,
+        KeyType::Empty => Key::Empty,
+        KeyType::Num => Key::Num(utils::read_i32(buf).ok()?),
+        KeyType::Str => Key::Str(utils::read_key(buf).ok()?),
+    };
+    let buf = &buf[key.size()..];
+    let raw = &buf[..utils::read_value_len(buf).ok()?];
+
+    let item = (key, offset + key.size(), raw);
+
+    Some((key.size() + raw.len(), item))
 }
 
 impl<'a> Iterator for RawIterator<'a> {
     type Item = (Key<'a>, Value<'a>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cursor >= self.container.len.value() {
-            return None;
+        self.next_item().map(decode_item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // skipped items are not decoded
+        for _ in 0..n {
+            self.next_item()?;
         }
-        let buf = &self.container.as_bytes()[self.cursor..];
-        let key = match self.key_type {
-            KeyType::Empty => Key::Empty,
-            KeyType::Num => {
-                let num = utils::read_i32(buf).ok()?;
-                Key::Num(num)
-            }
-            KeyType::Str => {
-                let key = utils::read_key(buf).ok()?;
-                Key::Str(key)
-            }
-        };
-        self.cursor += key.size();
-        let buf = &self.container.as_bytes()[self.cursor..];
-        let value = Value::deserialize(buf).ok()?;
-        self.cursor += value.total_size();
 
-        Some((key, value))
+        self.next()
     }
 }
+
+impl DoubleEndedIterator for RawIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_item().map(decode_item)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.next_back_item()?;
+        }
+
+        self.next_back()
+    }
+}
+
+/// Decodes value of item from container that is already validated
+fn decode_item((key, _, raw): RawItem<'_>) -> (Key<'_>, Value<'_>) {
+    (key, Value::deserialize_valid(raw))
+}
+
+impl ExactSizeIterator for RawIterator<'_> {}
+
+impl FusedIterator for RawIterator<'_> {}
+
+/// Iterator over items of container with their offsets and serialized values
+#[derive(Clone, Debug)]
+pub struct RawItems<'a>(RawIterator<'a>);
+
+impl<'a> Iterator for RawItems<'a> {
+    type Item = RawItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_item()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RawItems<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back_item()
+    }
+}
+
+impl ExactSizeIterator for RawItems<'_> {}
+
+impl FusedIterator for RawItems<'_> {}
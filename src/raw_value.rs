@@ -0,0 +1,143 @@
+use crate::data_type::{self, Type};
+use crate::error::Result;
+use crate::raw_container::{Key, KeyType, RawIterator};
+use crate::{utils, Storage, SubType, Value};
+use core::fmt;
+use core::ops::Index;
+
+/// Serialized value which is decoded on demand
+///
+/// References to raw values are returned when containers or values are
+/// indexed, so nested items can be accessed without decoding containers
+/// on the way:
+///
+/// ```
+/// use binn_rs::{binn, Value};
+///
+/// let mut buf = [0; 64];
+/// let obj = binn!(buf.as_mut_slice(), {
+///     "config": { "sensors": [3u8, 4u8] },
+/// })
+/// .unwrap();
+///
+/// assert_eq!(obj["config"]["sensors"][1], Value::UInt8(4));
+///
+/// let value = Value::from(obj);
+/// assert_eq!(value["config"]["sensors"][0].decode(), Value::UInt8(3));
+///
+/// // missing items and items of other kinds are null
+/// assert!(value["config"]["missing"][0].is_null());
+/// assert!(value["config"]["sensors"]["key"].is_null());
+/// ```
+#[derive(Eq, PartialEq)]
+#[repr(transparent)]
+pub struct RawValue([u8]);
+
+impl RawValue {
+    /// Null value that is returned when indexed item doesn't exist
+    pub(crate) const NULL: &'static RawValue = RawValue::new_unchecked(&[0x00]);
+
+    /// Reads value serialized at the beginning of given bytes
+    ///
+    /// Bytes after value are not included
+    pub fn from_bytes(bytes: &[u8]) -> Result<&RawValue> {
+        let bytes = &bytes[..utils::read_value_len(bytes)?];
+        Value::deserialize(bytes)?;
+
+        Ok(RawValue::new_unchecked(bytes))
+    }
+
+    /// Returns bytes of serialized value
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns storage and sub type of value without decoding it
+    pub fn peek_type(&self) -> (Storage, SubType) {
+        // bytes are always checked when raw value is created
+        let data_type = Type::try_from(&self.0).unwrap();
+
+        (data_type.storage, data_type.subtype)
+    }
+
+    /// Decodes value
+    pub fn decode(&self) -> Value<'_> {
+        // bytes are always checked when raw value is created
+        Value::deserialize_valid(&self.0)
+    }
+
+    /// Returns `true` if value is null
+    pub fn is_null(&self) -> bool {
+        Type::try_from(&self.0[..]).is_ok_and(|t| t == data_type::NULL)
+    }
+
+    /// Wraps bytes of single valid value
+    #[allow(unsafe_code)]
+    pub(crate) const fn new_unchecked(bytes: &[u8]) -> &RawValue {
+        // SAFETY: RawValue is transparent wrapper over [u8]
+        unsafe { &*(bytes as *const [u8] as *const RawValue) }
+    }
+
+    /// Iterates over items of this value if it's container of given type
+    fn items(
+        &self,
+        data_type: Type,
+        key_type: KeyType,
+    ) -> Option<impl Iterator<Item = (Key<'_>, &RawValue)>> {
+        if Type::try_from(&self.0).ok()? != data_type {
+            return None;
+        }
+
+        let items = RawIterator::new(&self.0, key_type).raw();
+        Some(items.map(|(key, raw)| (key, RawValue::new_unchecked(raw))))
+    }
+
+    /// Returns item with given key if this value is container of given type
+    fn item(&self, data_type: Type, key: Key<'_>) -> &RawValue {
+        self.items(data_type, key.key_type())
+            .and_then(|mut items| items.find(|(item_key, _)| *item_key == key))
+            .map_or(RawValue::NULL, |(_, value)| value)
+    }
+}
+
+impl fmt::Debug for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawValue").field(&self.decode()).finish()
+    }
+}
+
+impl<'a> PartialEq<Value<'a>> for RawValue {
+    fn eq(&self, other: &Value<'a>) -> bool {
+        self.decode() == *other
+    }
+}
+
+/// Indexes list by position or map by key
+///
+/// Returns null if value is neither a list nor a map or there is no such item
+impl Index<i32> for RawValue {
+    type Output = RawValue;
+
+    fn index(&self, index: i32) -> &RawValue {
+        let list_item = || {
+            let pos = usize::try_from(index).ok()?;
+            self.items(data_type::LIST, KeyType::Empty)?
+                .nth(pos)
+                .map(|(_, value)| value)
+        };
+
+        match Type::try_from(&self.0) {
+            Ok(data_type::LIST) => list_item().unwrap_or(RawValue::NULL),
+            _ => self.item(data_type::MAP, Key::Num(index)),
+        }
+    }
+}
+
+/// Returns null if value is not an object or there is no item with given key
+impl Index<&str> for RawValue {
+    type Output = RawValue;
+
+    fn index(&self, key: &str) -> &RawValue {
+        self.item(data_type::OBJECT, Key::Str(key))
+    }
+}
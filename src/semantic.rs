@@ -0,0 +1,141 @@
+//! Equality and hashing of values that doesn't depend on their encoding
+use crate::canonical::SortedIter;
+use crate::raw_container::{KeyType, RawContainer};
+use crate::Value;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+/// Numeric value of integer or floating point number
+#[derive(Eq, Hash, PartialEq)]
+enum Number {
+    Int(i128),
+    /// Only used for numbers that don't fit into `i128`
+    UInt(u128),
+    /// Bits of number converted to `f64`
+    Float(u64),
+}
+
+impl Number {
+    fn of(value: &Value<'_>) -> Option<Self> {
+        let number = match value {
+            Value::UInt128(v) => match i128::try_from(*v) {
+                Ok(v) => Number::Int(v),
+                Err(_) => Number::UInt(*v),
+            },
+            Value::Int128(v) => Number::Int(*v),
+            Value::Float(v) => Number::Float((*v as f64).to_bits()),
+            Value::Double(v) => Number::Float(v.to_bits()),
+            value => Number::Int(value.int_value()?),
+        };
+
+        Some(number)
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Checks whether two values are semantically equal
+    ///
+    /// Encoding of values is insignificant:
+    /// * integers of any width (including 128 bits) are equal if they hold the same number
+    /// * `Float` and `Double` are equal if they hold the same number, numbers are compared
+    ///   bitwise, so `NaN` is equal to itself and `0.0` is not equal to `-0.0`
+    /// * items of objects and maps can be in any order
+    ///
+    /// Integers are never equal to floating point numbers. This equality is used
+    /// by [`SemanticValue`].
+    pub fn semantic_eq(&self, other: &Value<'_>) -> bool {
+        if let Some(eq) = self.containers_eq(other, |a, b| a.semantic_eq(b)) {
+            return eq;
+        }
+
+        match (Number::of(self), Number::of(other)) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self == other,
+            _ => false,
+        }
+    }
+}
+
+/// Wrapper around [`Value`] that implements `Eq` and `Hash` with
+/// [semantic equality](Value::semantic_eq)
+///
+/// Can be used as key of hash map or set:
+/// ```
+/// use binn_rs::{SemanticValue, Value};
+/// use std::collections::HashSet;
+///
+/// let mut keys = HashSet::new();
+/// keys.insert(SemanticValue(Value::UInt8(5)));
+///
+/// assert!(keys.contains(&SemanticValue(Value::Int64(5))));
+/// assert!(!keys.contains(&SemanticValue(Value::Double(5.0))));
+/// ```
+#[derive(Debug)]
+pub struct SemanticValue<'a>(pub Value<'a>);
+
+impl PartialEq for SemanticValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.semantic_eq(&other.0)
+    }
+}
+
+impl Eq for SemanticValue<'_> {}
+
+impl Hash for SemanticValue<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state);
+    }
+}
+
+impl<'a> From<Value<'a>> for SemanticValue<'a> {
+    fn from(value: Value<'a>) -> Self {
+        SemanticValue(value)
+    }
+}
+
+fn hash_value<H: Hasher>(value: &Value<'_>, state: &mut H) {
+    if let Some(number) = Number::of(value) {
+        number.hash(state);
+        return;
+    }
+
+    mem::discriminant(value).hash(state);
+    match value {
+        Value::Null | Value::True | Value::False => {}
+        Value::Text(t)
+        | Value::DateTime(t)
+        | Value::Date(t)
+        | Value::Time(t)
+        | Value::DecimalStr(t) => t.hash(state),
+        Value::Blob(b) => b.hash(state),
+        Value::List(list) => hash_container(&list.inner, state),
+        Value::Map(map) => hash_container(&map.inner, state),
+        Value::Object(obj) => hash_container(&obj.inner, state),
+        Value::UserContainer(container) => hash_container(&container.inner, state),
+        Value::Empty(sub) => sub.hash(state),
+        Value::Byte(sub, v) => (sub, v).hash(state),
+        Value::Word(sub, v) => (sub, v).hash(state),
+        Value::DWord(sub, v) => (sub, v).hash(state),
+        Value::QWord(sub, v) => (sub, v).hash(state),
+        Value::UserText(sub, t) => (sub, t).hash(state),
+        Value::UserBlob(sub, b) => (sub, b).hash(state),
+        // numbers are already hashed
+        _ => unreachable!(),
+    }
+}
+
+/// Hashes items of container in canonical order, so hash doesn't depend on order of keys
+fn hash_container<H: Hasher>(container: &RawContainer<'_>, state: &mut H) {
+    container.data_type().hash(state);
+    container.count().hash(state);
+
+    if container.key_type() == KeyType::Unknown {
+        container.as_bytes().hash(state);
+        return;
+    }
+
+    for (key, value) in SortedIter::new(container) {
+        key.hash(state);
+        hash_value(&value, state);
+    }
+}
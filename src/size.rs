@@ -1,6 +1,5 @@
 use crate::error::{OutOfRangeError, Result};
-use crate::{utils, Error};
-use byteorder::{BigEndian, ByteOrder};
+use crate::Error;
 
 /// Maximum possible size that can be used
 pub const MAX_SIZE: u32 = 0x7FFFFFFF;
@@ -15,15 +14,11 @@ pub enum Size {
 }
 
 impl Size {
-    pub fn is_compactable(value: usize) -> bool {
+    pub const fn is_compactable(value: usize) -> bool {
         value <= 127
     }
 
-    pub fn is_u8(&self) -> bool {
-        self.size() == 1
-    }
-
-    pub fn new(value: usize) -> core::result::Result<Self, OutOfRangeError> {
+    pub const fn new(value: usize) -> core::result::Result<Self, OutOfRangeError> {
         if Self::is_compactable(value) {
             Ok(Size::Compact(value as u8))
         } else if value <= MAX_SIZE as usize {
@@ -34,23 +29,41 @@ impl Size {
     }
 
     /// How many bytes this size will take to serialize
-    pub fn size(&self) -> usize {
+    pub const fn size(&self) -> usize {
         match self {
             Size::Compact(_) => 1,
             Size::Full(_) => 4,
         }
     }
 
-    pub fn value(&self) -> usize {
+    pub const fn value(&self) -> usize {
         match self {
             Size::Compact(v) => *v as usize,
             Size::Full(v) => *v as usize,
         }
     }
 
+    /// Parses size at the beginning of given bytes
+    pub const fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.is_empty() {
+            return Err(Error::Malformed);
+        }
+
+        if (bytes[0] & 0x80) == 0 {
+            // compact form is used
+            Ok(Size::Compact(bytes[0]))
+        } else if bytes.len() >= 4 {
+            let v = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            // remove first bit
+            Ok(Size::Full(v & MAX_SIZE))
+        } else {
+            Err(Error::Malformed)
+        }
+    }
+
     /// Writes this size to given buffer in its compact or full form
     /// and returns next insert position
-    pub fn write<'b>(&self, buf: &'b mut [u8]) -> Result<&'b mut [u8]> {
+    pub const fn write<'b>(&self, buf: &'b mut [u8]) -> Result<&'b mut [u8]> {
         let total_size = self.size();
 
         if buf.len() < total_size {
@@ -62,11 +75,15 @@ impl Size {
                 buf[0] = *v;
             }
             Size::Full(v) => {
-                BigEndian::write_u32(buf, *v | MAX_SIZE_MASK);
+                let bytes = (*v | MAX_SIZE_MASK).to_be_bytes();
+                buf[0] = bytes[0];
+                buf[1] = bytes[1];
+                buf[2] = bytes[2];
+                buf[3] = bytes[3];
             }
         }
 
-        Ok(&mut buf[total_size..])
+        Ok(buf.split_at_mut(total_size).1)
     }
 }
 
@@ -74,18 +91,6 @@ impl<'a> TryFrom<&'a [u8]> for Size {
     type Error = Error;
 
     fn try_from(bytes: &'a [u8]) -> Result<Self> {
-        if bytes.is_empty() {
-            return Err(Error::Malformed);
-        }
-
-        if (bytes[0] & 0x80) == 0 {
-            // compact form is used
-            Ok(Size::Compact(bytes[0]))
-        } else if let Ok(v) = utils::read_u32(bytes) {
-            // remove first bit
-            Ok(Size::Full(v & MAX_SIZE))
-        } else {
-            Err(Error::Malformed)
-        }
+        Self::parse(bytes)
     }
 }
@@ -1,19 +1,37 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// How data of binn value is stored
+///
+/// Storage is encoded in type of each value along with its [`SubType`](crate::SubType)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Storage {
+    /// No data, type alone defines value
     NoBytes = 0x00,
+
+    /// Single byte
     Byte = 0x20,
+
+    /// Two bytes (16bits)
     Word = 0x40,
+
+    /// Four bytes (32bits)
     DWord = 0x60,
+
+    /// Eight bytes (64bits)
     QWord = 0x80,
+
+    /// UTF-8 string with null terminator
     String = 0xA0,
+
+    /// Arbitrary binary data
     Blob = 0xC0,
+
+    /// Container with other values
     Container = 0xE0,
 }
 
 impl Storage {
     /// Return how many bytes this storage takes if it is fixed size.
     /// Otherwise returns None.
-    pub fn fixed_size(&self) -> Option<usize> {
+    pub const fn fixed_size(&self) -> Option<usize> {
         match self {
             Storage::NoBytes => Some(0),
             Storage::Byte => Some(1),
@@ -23,12 +41,9 @@ impl Storage {
             Storage::String | Storage::Blob | Storage::Container => None,
         }
     }
-}
 
-impl TryFrom<u8> for Storage {
-    type Error = ();
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+    /// Returns storage that is encoded by given bits of type
+    pub(crate) const fn from_u8(value: u8) -> Option<Self> {
         let storage = match value {
             0x00 => Storage::NoBytes,
             0x20 => Storage::Byte,
@@ -38,8 +53,16 @@ impl TryFrom<u8> for Storage {
             0xA0 => Storage::String,
             0xC0 => Storage::Blob,
             0xE0 => Storage::Container,
-            _ => return Err(()),
+            _ => return None,
         };
-        Ok(storage)
+        Some(storage)
+    }
+}
+
+impl TryFrom<u8> for Storage {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(())
     }
 }
@@ -10,7 +10,7 @@
 ///
 /// let value = Value::UserText(CUSTOM_TEXT, "some text that means something");
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct SubType(pub(crate) u16);
 
 macro_rules! impl_try_from {
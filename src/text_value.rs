@@ -0,0 +1,103 @@
+use crate::error::Result;
+use crate::Value;
+use core::fmt;
+
+/// How many bytes formatted text can take
+const CAPACITY: usize = 40;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TextKind {
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    DateTime,
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    Date,
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    Time,
+    #[cfg(feature = "rust_decimal")]
+    DecimalStr,
+}
+
+/// Formatted text of `DateTime`, `Date`, `Time` or `DecimalStr` value
+///
+/// [`Value`] only borrows its text, so there is no `From<Decimal> for Value`
+/// (or for dates and times): formatted text would have no owner to borrow
+/// from. Instead typed values are first formatted into this small buffer,
+/// which is kept by caller while value is added:
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use binn_rs::{Object, TextValue, Value};
+/// use chrono::NaiveDate;
+///
+/// let mut buf = [0; 64];
+/// let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+///
+/// let date = NaiveDate::from_ymd_opt(2023, 4, 15).unwrap();
+/// obj.add_value("date", &TextValue::from(date)).unwrap();
+///
+/// assert_eq!(obj.get("date").unwrap(), Value::Date("2023-04-15"));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TextValue {
+    kind: TextKind,
+    buf: [u8; CAPACITY],
+    len: usize,
+}
+
+impl TextValue {
+    /// Returns formatted text
+    pub fn as_str(&self) -> &str {
+        // only valid utf-8 is written by fmt::Write
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+
+    /// Returns binn value that borrows formatted text
+    pub fn as_value(&self) -> Value<'_> {
+        let text = self.as_str();
+        match self.kind {
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            TextKind::DateTime => Value::DateTime(text),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            TextKind::Date => Value::Date(text),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            TextKind::Time => Value::Time(text),
+            #[cfg(feature = "rust_decimal")]
+            TextKind::DecimalStr => Value::DecimalStr(text),
+        }
+    }
+
+    /// Formats text with given function
+    ///
+    /// # Panics
+    ///
+    /// Panics if formatted text doesn't fit into buffer, which should never
+    /// happen for supported types
+    pub(crate) fn format(kind: TextKind, f: impl FnOnce(&mut TextValue) -> Result<()>) -> Self {
+        let mut text = TextValue {
+            kind,
+            buf: [0; CAPACITY],
+            len: 0,
+        };
+        f(&mut text).expect("formatted text is too long");
+        text
+    }
+}
+
+impl fmt::Write for TextValue {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > CAPACITY {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a TextValue> for Value<'a> {
+    fn from(value: &'a TextValue) -> Self {
+        value.as_value()
+    }
+}
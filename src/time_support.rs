@@ -0,0 +1,97 @@
+use crate::datetime::{self, Date, Time};
+use crate::error::{Error, Result};
+use crate::text_value::{TextKind, TextValue};
+use crate::Value;
+
+fn to_date(date: &time::Date) -> Date {
+    Date {
+        year: date.year(),
+        month: date.month() as u8,
+        day: date.day(),
+    }
+}
+
+fn to_time(time: &time::Time) -> Time {
+    Time {
+        hour: time.hour(),
+        minute: time.minute(),
+        second: time.second(),
+        nanosecond: time.nanosecond(),
+    }
+}
+
+fn from_date(date: Date) -> Result<time::Date> {
+    let month = time::Month::try_from(date.month).map_err(|_| Error::InvalidFormat)?;
+    time::Date::from_calendar_date(date.year, month, date.day).map_err(|_| Error::InvalidFormat)
+}
+
+fn from_time(time: Time) -> Result<time::Time> {
+    // leap seconds are not supported by time crate so they are rejected here
+    time::Time::from_hms_nano(time.hour, time.minute, time.second, time.nanosecond)
+        .map_err(|_| Error::InvalidFormat)
+}
+
+fn from_date_time((date, time): (Date, Time)) -> Result<time::PrimitiveDateTime> {
+    Ok(time::PrimitiveDateTime::new(
+        from_date(date)?,
+        from_time(time)?,
+    ))
+}
+
+impl From<time::PrimitiveDateTime> for TextValue {
+    fn from(value: time::PrimitiveDateTime) -> Self {
+        TextValue::format(TextKind::DateTime, |text| {
+            datetime::write_date_time(&to_date(&value.date()), &to_time(&value.time()), text)
+        })
+    }
+}
+
+impl From<time::Date> for TextValue {
+    fn from(value: time::Date) -> Self {
+        TextValue::format(TextKind::Date, |text| to_date(&value).write(text))
+    }
+}
+
+impl From<time::Time> for TextValue {
+    fn from(value: time::Time) -> Self {
+        TextValue::format(TextKind::Time, |text| to_time(&value).write(text))
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for time::PrimitiveDateTime {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::DateTime(text) = value {
+            datetime::parse_date_time(text)
+                .and_then(from_date_time)
+                .map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for time::Date {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::Date(text) = value {
+            Date::parse(text).and_then(from_date).map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for time::Time {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::Time(text) = value {
+            Time::parse(text).and_then(from_time).map_err(|_| value)
+        } else {
+            Err(value)
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::error::{Error, Result};
+use crate::storage::Storage;
+use crate::subtype::SubType;
+use crate::Value;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for () {}
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+    impl Sealed for &str {}
+    impl Sealed for &[u8] {}
+}
+
+/// Rust type that is used as data of user-defined binn value
+///
+/// Implemented for each binn storage:
+///
+/// | Type       | Storage              |
+/// |------------|----------------------|
+/// | `()`       | [`Storage::NoBytes`] |
+/// | `u8`       | [`Storage::Byte`]    |
+/// | `u16`      | [`Storage::Word`]    |
+/// | `u32`      | [`Storage::DWord`]   |
+/// | `u64`      | [`Storage::QWord`]   |
+/// | `&str`     | [`Storage::String`]  |
+/// | `&[u8]`    | [`Storage::Blob`]    |
+pub trait UserStorage<'a>: private::Sealed + Sized {
+    /// Storage that is used for this data in binn
+    const STORAGE: Storage;
+
+    /// Creates user-defined value with given subtype and this data
+    fn into_value(self, subtype: SubType) -> Value<'a>;
+
+    /// Extracts subtype and data from user-defined value
+    ///
+    /// Returns `None` if value is not user-defined or uses different storage
+    fn from_value(value: &Value<'a>) -> Option<(SubType, Self)>;
+}
+
+macro_rules! user_storage_impl {
+    ($data_type:ty, $storage:ident, $enum_name:ident) => {
+        impl<'a> UserStorage<'a> for $data_type {
+            const STORAGE: Storage = Storage::$storage;
+
+            fn into_value(self, subtype: SubType) -> Value<'a> {
+                Value::$enum_name(subtype, self)
+            }
+
+            fn from_value(value: &Value<'a>) -> Option<(SubType, Self)> {
+                if let Value::$enum_name(subtype, data) = value {
+                    Some((*subtype, *data))
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+user_storage_impl!(u8, Byte, Byte);
+user_storage_impl!(u16, Word, Word);
+user_storage_impl!(u32, DWord, DWord);
+user_storage_impl!(u64, QWord, QWord);
+user_storage_impl!(&'a str, String, UserText);
+user_storage_impl!(&'a [u8], Blob, UserBlob);
+
+impl<'a> UserStorage<'a> for () {
+    const STORAGE: Storage = Storage::NoBytes;
+
+    fn into_value(self, subtype: SubType) -> Value<'a> {
+        Value::Empty(subtype)
+    }
+
+    fn from_value(value: &Value<'a>) -> Option<(SubType, Self)> {
+        if let Value::Empty(subtype) = value {
+            Some((*subtype, ()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Rust type that is stored in binn as user-defined primitive value
+///
+/// Such types can be passed directly to `add_value` and read back
+/// with [`Value::decode_user`]:
+/// ```
+/// use binn_rs::{BinnUserType, Error, List, SubType};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Celsius(i16);
+///
+/// impl BinnUserType<'_> for Celsius {
+///     const SUB_TYPE: SubType = SubType::new(3);
+///     type Data = u16;
+///
+///     fn encode(&self) -> u16 {
+///         self.0 as u16
+///     }
+///
+///     fn decode(data: u16) -> Result<Self, Error> {
+///         Ok(Celsius(data as i16))
+///     }
+/// }
+///
+/// let mut buf = [0; 16];
+/// let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+/// list.add_value(Celsius(-5)).unwrap();
+///
+/// let value = list.get(0).unwrap();
+/// assert_eq!(value.decode_user::<Celsius>().unwrap(), Celsius(-5));
+/// ```
+pub trait BinnUserType<'a>: Sized {
+    /// Subtype that identifies this type in binn
+    ///
+    /// It must not be used by predefined types with the same storage
+    /// (e.g. subtype 0 of `Byte` storage is `UInt8`), otherwise value
+    /// will be read back as predefined type
+    const SUB_TYPE: SubType;
+
+    /// Data of this type, defines which storage is used
+    type Data: UserStorage<'a>;
+
+    /// Converts this value to data that is written to binn
+    fn encode(&self) -> Self::Data;
+
+    /// Creates value from data that was read from binn
+    fn decode(data: Self::Data) -> Result<Self>;
+}
+
+impl<'a, T: BinnUserType<'a>> From<T> for Value<'a> {
+    fn from(value: T) -> Self {
+        value.encode().into_value(T::SUB_TYPE)
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Decodes user-defined value as given type
+    ///
+    /// Returns [`Error::TypeMismatch`] if value is not user-defined value
+    /// with the same storage and subtype as `T`
+    pub fn decode_user<T: BinnUserType<'a>>(&self) -> Result<T> {
+        match T::Data::from_value(self) {
+            Some((subtype, data)) if subtype == T::SUB_TYPE => T::decode(data),
+            _ => Err(Error::TypeMismatch),
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::data_type::Type;
 use crate::error::Error;
 use crate::error::Result;
 use crate::size::Size;
+use crate::storage::Storage;
 
 macro_rules! read_num_impl {
     ($name:ident) => {
@@ -22,6 +24,8 @@ read_num_impl!(u32);
 read_num_impl!(i32);
 read_num_impl!(u64);
 read_num_impl!(i64);
+read_num_impl!(u128);
+read_num_impl!(i128);
 
 read_num_impl!(f32);
 read_num_impl!(f64);
@@ -42,40 +46,74 @@ pub fn read_i8(buf: &[u8]) -> Result<i8> {
     Ok(buf[0] as i8)
 }
 
-/// Reads single key from buffer and returns it with how many bytes were read
-pub fn read_key(buf: &[u8]) -> Result<&str> {
-    if buf.is_empty() {
+/// Returns part of buffer after given offset
+pub const fn tail(buf: &[u8], offset: usize) -> Result<&[u8]> {
+    if buf.len() < offset {
         return Err(Error::Malformed);
     }
-    let len = buf[0] as usize;
-    let buf = &buf[1..];
+
+    Ok(buf.split_at(offset).1)
+}
+
+/// Returns first `len` bytes of buffer
+pub const fn head(buf: &[u8], len: usize) -> Result<&[u8]> {
     if buf.len() < len {
         return Err(Error::Malformed);
     }
 
-    let key = core::str::from_utf8(&buf[..len]).map_err(|_| Error::Malformed)?;
-
-    Ok(key)
+    Ok(buf.split_at(len).0)
 }
 
-pub fn read_text(buf: &[u8]) -> Result<&str> {
-    let size = Size::try_from(buf).map_err(|_| Error::Malformed)?;
-    let buf = &buf[size.size()..];
-    if buf.len() < size.value() {
+/// Reads how many bytes value serialized at start of given buffer takes
+///
+/// Only [type] and [size] of value are read, so its data isn't validated
+pub const fn read_value_len(buf: &[u8]) -> Result<usize> {
+    let data_type = tri!(Type::parse(buf));
+    let type_size = data_type.size();
+
+    let len = match data_type.storage.fixed_size() {
+        Some(data_size) => type_size + data_size,
+        None => {
+            let size = tri!(Size::parse(tri!(tail(buf, type_size))));
+            match data_type.storage {
+                // [size] of container includes its header
+                Storage::Container if size.value() < type_size + 2 => return Err(Error::Malformed),
+                Storage::Container => size.value(),
+                // [size] of string doesn't include null terminator
+                Storage::String => type_size + size.size() + size.value() + 1,
+                _ => type_size + size.size() + size.value(),
+            }
+        }
+    };
+
+    if buf.len() < len {
         return Err(Error::Malformed);
     }
 
-    let text = core::str::from_utf8(&buf[..size.value()]).map_err(|_| Error::Malformed)?;
-
-    Ok(text)
+    Ok(len)
 }
 
-pub fn read_blob(buf: &[u8]) -> Result<&[u8]> {
-    let size = Size::try_from(buf).map_err(|_| Error::Malformed)?;
-    let buf = &buf[size.size()..];
-    if buf.len() < size.value() {
+/// Reads single key from buffer and returns it with how many bytes were read
+pub const fn read_key(buf: &[u8]) -> Result<&str> {
+    if buf.is_empty() {
         return Err(Error::Malformed);
     }
+    let key = tri!(head(tri!(tail(buf, 1)), buf[0] as usize));
+
+    match core::str::from_utf8(key) {
+        Ok(key) => Ok(key),
+        Err(_) => Err(Error::Malformed),
+    }
+}
+
+pub const fn read_text(buf: &[u8]) -> Result<&str> {
+    match core::str::from_utf8(tri!(read_blob(buf))) {
+        Ok(text) => Ok(text),
+        Err(_) => Err(Error::Malformed),
+    }
+}
 
-    Ok(&buf[..size.value()])
+pub const fn read_blob(buf: &[u8]) -> Result<&[u8]> {
+    let size = tri!(Size::parse(buf));
+    head(tri!(tail(buf, size.size())), size.value())
 }
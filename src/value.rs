@@ -1,8 +1,9 @@
 use crate::data_type::Type;
 use crate::storage::Storage;
 use crate::subtype::SubType;
-use crate::{data_type, utils, Error, List, Map, Object};
+use crate::{canonical, data_type, utils, Error, List, Map, Object, RawValue, UserContainer};
 use byteorder::{BigEndian, ByteOrder};
+use core::ops::Index;
 
 use crate::error::Result;
 use crate::raw_container::{KeyType, RawContainer};
@@ -68,6 +69,20 @@ pub enum Value<'a> {
     /// Binary data
     Blob(&'a [u8]),
 
+    /// Unsigned 128bit integer
+    ///
+    /// Binn has no native 128bit storage, so it is stored as blob with
+    /// subtype 128 that holds exactly 16 bytes in big-endian order. Blobs
+    /// with this subtype and other length are read as [`Value::UserBlob`]
+    UInt128(u128),
+
+    /// Signed 128bit integer
+    ///
+    /// Binn has no native 128bit storage, so it is stored as blob with
+    /// subtype 129 that holds exactly 16 bytes in big-endian order. Blobs
+    /// with this subtype and other length are read as [`Value::UserBlob`]
+    Int128(i128),
+
     /// Container that stores elements sequentially without keys
     List(List<'a>),
 
@@ -98,11 +113,31 @@ pub enum Value<'a> {
 
     /// User-defined type with Blob storage (binary data)
     UserBlob(SubType, &'a [u8]),
+
+    /// User-defined type with Container storage
+    UserContainer(UserContainer<'a>),
 }
 
 impl<'a> Value<'a> {
     /// Try to deserialize given bytes as binn value
     pub fn deserialize(bytes: &'a [u8]) -> Result<Self> {
+        Self::read(bytes, RawContainer::from_bytes)
+    }
+
+    /// Deserializes value that is already validated
+    ///
+    /// Nested containers are not validated again
+    pub(crate) fn deserialize_valid(bytes: &'a [u8]) -> Self {
+        let container = |bytes, key_type| Ok(RawContainer::from_valid_bytes(bytes, key_type));
+
+        Self::read(bytes, container).expect("value is already validated")
+    }
+
+    /// Deserializes value, containers are created with given function
+    fn read(
+        bytes: &'a [u8],
+        container: impl Fn(&'a [u8], KeyType) -> Result<RawContainer<'a>>,
+    ) -> Result<Self> {
         let data_type: Type = bytes.try_into()?;
         let value = &bytes[data_type.size()..];
 
@@ -126,6 +161,18 @@ impl<'a> Value<'a> {
             data_type::TIME => return Ok(Value::Time(utils::read_text(value)?)),
             data_type::DECIMAL_STR => return Ok(Value::DecimalStr(utils::read_text(value)?)),
             data_type::BLOB => return Ok(Value::Blob(utils::read_blob(value)?)),
+            data_type::UINT128 | data_type::INT128 => {
+                let blob = utils::read_blob(value)?;
+                // only blobs of exactly 16 bytes hold 128bit integer,
+                // anything else is kept as is
+                return Ok(match blob.len() {
+                    16 if data_type == data_type::UINT128 => {
+                        Value::UInt128(utils::read_u128(blob)?)
+                    }
+                    16 => Value::Int128(utils::read_i128(blob)?),
+                    _ => Value::UserBlob(data_type.subtype, blob),
+                });
+            }
             Type {
                 storage: Storage::NoBytes,
                 subtype,
@@ -164,28 +211,244 @@ impl<'a> Value<'a> {
 
         match data_type {
             data_type::LIST => Ok(Value::List(List {
-                inner: RawContainer::from_bytes(bytes, KeyType::Empty)?,
+                inner: container(bytes, KeyType::Empty)?,
             })),
             data_type::MAP => Ok(Value::Map(Map {
-                inner: RawContainer::from_bytes(bytes, KeyType::Num)?,
+                inner: container(bytes, KeyType::Num)?,
             })),
             data_type::OBJECT => Ok(Value::Object(Object {
-                inner: RawContainer::from_bytes(bytes, KeyType::Str)?,
+                inner: container(bytes, KeyType::Str)?,
             })),
             Type {
                 storage: Storage::Container,
                 subtype: _,
-            } => Err(Error::Malformed),
+            } => Ok(Value::UserContainer(UserContainer {
+                inner: container(bytes, KeyType::Unknown)?,
+            })),
             _ => unreachable!(),
         }
     }
 
+    /// Returns storage and sub type of value serialized at start of given bytes
+    ///
+    /// Only type of value is read, value itself isn't validated,
+    /// so it's cheap way to find out how bytes should be read.
+    ///
+    /// ```
+    /// use binn_rs::{Storage, SubType, Value};
+    ///
+    /// // user-defined container with sub type 20, rest of it isn't checked
+    /// let bytes = [0xF0, 0x14, 0xFF];
+    /// let (storage, subtype) = Value::peek_type(&bytes).unwrap();
+    ///
+    /// assert_eq!(storage, Storage::Container);
+    /// assert_eq!(subtype, SubType::new(20));
+    /// ```
+    pub fn peek_type(bytes: &[u8]) -> Result<(Storage, SubType)> {
+        let data_type = Type::try_from(bytes)?;
+
+        Ok((data_type.storage, data_type.subtype))
+    }
+
+    /// Returns how many bytes this value takes in serialized form
+    pub fn encoded_len(&self) -> usize {
+        self.total_size()
+    }
+
+    /// Serializes this value into given buffer and returns how many bytes were written
+    ///
+    /// Any value can be serialized, not only containers. Returns
+    /// [`Error::SmallBuffer`] if buffer can't hold [`encoded_len`](Self::encoded_len) bytes.
+    ///
+    /// ```
+    /// use binn_rs::Value;
+    ///
+    /// let mut buf = [0; 8];
+    /// let len = Value::UInt32(62626262).serialize_into(&mut buf).unwrap();
+    ///
+    /// assert_eq!(&buf[..len], &[0x60, 0x03, 0xBB, 0x99, 0xD6]);
+    /// assert_eq!(Value::deserialize(&buf[..len]).unwrap(), Value::UInt32(62626262));
+    /// ```
+    pub fn serialize_into(&self, buf: &mut [u8]) -> Result<usize> {
+        self.write(buf).map(|(_, size)| size)
+    }
+
+    /// Serializes this value into new vector
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut buf = alloc::vec![0; self.encoded_len()];
+        // buffer has exact size, so no error is possible
+        self.serialize_into(&mut buf).unwrap();
+
+        buf
+    }
+
+    /// Returns copy of this value that borrows data from this value
+    ///
+    /// Containers are returned as read-only
+    pub(crate) fn borrowed(&self) -> Value<'_> {
+        match self {
+            Value::Null => Value::Null,
+            Value::True => Value::True,
+            Value::False => Value::False,
+            Value::UInt8(v) => Value::UInt8(*v),
+            Value::Int8(v) => Value::Int8(*v),
+            Value::UInt16(v) => Value::UInt16(*v),
+            Value::Int16(v) => Value::Int16(*v),
+            Value::UInt32(v) => Value::UInt32(*v),
+            Value::Int32(v) => Value::Int32(*v),
+            Value::Float(v) => Value::Float(*v),
+            Value::UInt64(v) => Value::UInt64(*v),
+            Value::Int64(v) => Value::Int64(*v),
+            Value::Double(v) => Value::Double(*v),
+            Value::Text(v) => Value::Text(v),
+            Value::DateTime(v) => Value::DateTime(v),
+            Value::Date(v) => Value::Date(v),
+            Value::Time(v) => Value::Time(v),
+            Value::DecimalStr(v) => Value::DecimalStr(v),
+            Value::Blob(v) => Value::Blob(v),
+            Value::UInt128(v) => Value::UInt128(*v),
+            Value::Int128(v) => Value::Int128(*v),
+            Value::List(list) => Value::List(List {
+                inner: list.inner.read_only(),
+            }),
+            Value::Map(map) => Value::Map(Map {
+                inner: map.inner.read_only(),
+            }),
+            Value::Object(obj) => Value::Object(Object {
+                inner: obj.inner.read_only(),
+            }),
+            Value::Empty(sub) => Value::Empty(*sub),
+            Value::Byte(sub, v) => Value::Byte(*sub, *v),
+            Value::Word(sub, v) => Value::Word(*sub, *v),
+            Value::DWord(sub, v) => Value::DWord(*sub, *v),
+            Value::QWord(sub, v) => Value::QWord(*sub, *v),
+            Value::UserText(sub, v) => Value::UserText(*sub, v),
+            Value::UserBlob(sub, v) => Value::UserBlob(*sub, v),
+            Value::UserContainer(container) => Value::UserContainer(UserContainer {
+                inner: container.inner.read_only(),
+            }),
+        }
+    }
+
+    /// Checks whether canonical forms of two values are equal
+    ///
+    /// Integers of different types (including 128 bits) are equal if they hold
+    /// the same number and items of objects and maps can be in any order.
+    /// See [`Object::to_canonical`] for details about canonical form.
+    pub fn canonical_eq(&self, other: &Value<'_>) -> bool {
+        if let Some(eq) = self.containers_eq(other, |a, b| a.canonical_eq(b)) {
+            return eq;
+        }
+
+        match (self.to_canonical_int(), other.to_canonical_int()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self == other,
+            _ => false,
+        }
+    }
+
+    /// Compares items of containers of the same type in canonical order
+    ///
+    /// Returns `None` if values are not containers of the same type
+    pub(crate) fn containers_eq(
+        &self,
+        other: &Value<'_>,
+        value_eq: fn(&Value<'_>, &Value<'_>) -> bool,
+    ) -> Option<bool> {
+        let (a, b) = match (self, other) {
+            (Value::List(a), Value::List(b)) => (&a.inner, &b.inner),
+            (Value::Map(a), Value::Map(b)) => (&a.inner, &b.inner),
+            (Value::Object(a), Value::Object(b)) => (&a.inner, &b.inner),
+            (Value::UserContainer(a), Value::UserContainer(b)) => (&a.inner, &b.inner),
+            _ => return None,
+        };
+
+        Some(canonical::containers_eq(a, b, value_eq))
+    }
+
+    /// Returns numeric value of integer (up to 64 bits)
+    pub(crate) fn int_value(&self) -> Option<i128> {
+        let value = match self {
+            Value::UInt8(v) => *v as i128,
+            Value::Int8(v) => *v as i128,
+            Value::UInt16(v) => *v as i128,
+            Value::Int16(v) => *v as i128,
+            Value::UInt32(v) => *v as i128,
+            Value::Int32(v) => *v as i128,
+            Value::UInt64(v) => *v as i128,
+            Value::Int64(v) => *v as i128,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Creates integer value that uses smallest type that can hold given number
+    ///
+    /// Unsigned types are used for non-negative numbers and signed for negative.
+    /// Numbers that don't fit into 64 bits are stored as `UInt128` or `Int128`:
+    /// ```
+    /// use binn_rs::Value;
+    ///
+    /// assert_eq!(Value::compact_int(5i64), Value::UInt8(5));
+    /// assert_eq!(Value::compact_int(-300i64), Value::Int16(-300));
+    /// assert_eq!(Value::compact_int(u64::MAX), Value::UInt64(u64::MAX));
+    /// ```
+    pub fn compact_int(value: impl Into<i128>) -> Value<'static> {
+        let value = value.into();
+
+        if let Ok(v) = u8::try_from(value) {
+            Value::UInt8(v)
+        } else if let Ok(v) = u16::try_from(value) {
+            Value::UInt16(v)
+        } else if let Ok(v) = u32::try_from(value) {
+            Value::UInt32(v)
+        } else if let Ok(v) = u64::try_from(value) {
+            Value::UInt64(v)
+        } else if let Ok(v) = i8::try_from(value) {
+            Value::Int8(v)
+        } else if let Ok(v) = i16::try_from(value) {
+            Value::Int16(v)
+        } else if let Ok(v) = i32::try_from(value) {
+            Value::Int32(v)
+        } else if let Ok(v) = i64::try_from(value) {
+            Value::Int64(v)
+        } else if let Ok(v) = u128::try_from(value) {
+            Value::UInt128(v)
+        } else {
+            Value::Int128(value)
+        }
+    }
+
+    /// Returns integer (up to 64 bits) stored in smallest type that can hold it
+    ///
+    /// See [`Value::compact_int`]
+    pub(crate) fn to_smallest_int(&self) -> Option<Value<'static>> {
+        self.int_value().map(Value::compact_int)
+    }
+
+    /// Returns integer of any width (including 128 bits) stored in smallest
+    /// type that can hold it, as it's done by canonical encoding
+    ///
+    /// See [`Value::compact_int`]
+    pub(crate) fn to_canonical_int(&self) -> Option<Value<'static>> {
+        match self {
+            Value::UInt128(v) => match i128::try_from(*v) {
+                Ok(v) => Some(Value::compact_int(v)),
+                Err(_) => Some(Value::UInt128(*v)),
+            },
+            Value::Int128(v) => Some(Value::compact_int(*v)),
+            value => value.to_smallest_int(),
+        }
+    }
+
     /// Returns how many bytes \[data\] will take, when it needs \[size\] element
     ///
     /// # Panics
     ///
     /// Panics if value is container
-    pub(crate) fn data_size(&self) -> Option<usize> {
+    pub(crate) const fn data_size(&self) -> Option<usize> {
         match self {
             Value::Text(t)
             | Value::DateTime(t)
@@ -196,14 +459,38 @@ impl<'a> Value<'a> {
 
             Value::Blob(b) | Value::UserBlob(_, b) => Some(b.len()),
 
-            Value::List(_) | Value::Map(_) | Value::Object(_) => unreachable!(),
+            Value::UInt128(_) | Value::Int128(_) => Some(16),
+
+            Value::List(_) | Value::Map(_) | Value::Object(_) | Value::UserContainer(_) => {
+                unreachable!()
+            }
 
             _ => None,
         }
     }
 
+    /// Returns \[size\] element of value, when it needs one
+    ///
+    /// For string storage \[size\] doesn't count null terminator
+    ///
+    /// # Panics
+    ///
+    /// Panics if value is container or too big
+    pub(crate) const fn size_entry(&self) -> Option<Size> {
+        let size = match self.data_size() {
+            Some(size) if matches!(self.get_type().storage, Storage::String) => size - 1,
+            Some(size) => size,
+            None => return None,
+        };
+
+        match Size::new(size) {
+            Ok(size) => Some(size),
+            Err(_) => panic!("value is too big"),
+        }
+    }
+
     /// Returns type of this value (subtype, storage)
-    pub(crate) fn get_type(&self) -> Type {
+    pub(crate) const fn get_type(&self) -> Type {
         match self {
             Value::Null => data_type::NULL,
             Value::True => data_type::TRUE,
@@ -224,9 +511,12 @@ impl<'a> Value<'a> {
             Value::Time(_) => data_type::TIME,
             Value::DecimalStr(_) => data_type::DECIMAL_STR,
             Value::Blob(_) => data_type::BLOB,
-            Value::List(_) => data_type::LIST,
-            Value::Map(_) => data_type::MAP,
-            Value::Object(_) => data_type::OBJECT,
+            Value::UInt128(_) => data_type::UINT128,
+            Value::Int128(_) => data_type::INT128,
+            Value::List(list) => list.inner.data_type(),
+            Value::Map(map) => map.inner.data_type(),
+            Value::Object(obj) => obj.inner.data_type(),
+            Value::UserContainer(container) => container.inner.data_type(),
             Value::Empty(sub) => Type {
                 storage: Storage::NoBytes,
                 subtype: *sub,
@@ -265,6 +555,7 @@ impl<'a> Value<'a> {
             Value::List(list) => return list.as_bytes().len(),
             Value::Map(map) => return map.as_bytes().len(),
             Value::Object(obj) => return obj.as_bytes().len(),
+            Value::UserContainer(container) => return container.as_bytes().len(),
             _ => {}
         }
 
@@ -277,8 +568,8 @@ impl<'a> Value<'a> {
 
         if let Some(data_size) = fixed_size {
             type_size + data_size
-        } else if let Some(data_size) = data_size {
-            type_size + Size::new(data_size).unwrap().size() + data_size
+        } else if let (Some(data_size), Some(size)) = (data_size, self.size_entry()) {
+            type_size + size.size() + data_size
         } else {
             // value is either fixed size or arbitrary sized
             unreachable!()
@@ -307,6 +598,10 @@ impl<'a> Value<'a> {
                 buf[..total_size].copy_from_slice(obj.as_bytes());
                 return Ok((&mut buf[total_size..], total_size));
             }
+            Value::UserContainer(container) => {
+                buf[..total_size].copy_from_slice(container.as_bytes());
+                return Ok((&mut buf[total_size..], total_size));
+            }
             _ => {}
         }
 
@@ -319,12 +614,8 @@ impl<'a> Value<'a> {
         let mut buf = value_type.write(buf);
 
         // write [size] if present
-        if let Some(mut data_size) = data_size {
-            if value_type.storage == Storage::String {
-                // for string storage we should not count null terminator in [size] item
-                data_size -= 1;
-            }
-            buf = Size::new(data_size).unwrap().write(buf).unwrap();
+        if let Some(size) = self.size_entry() {
+            buf = size.write(buf).unwrap();
         }
 
         // write [data] if present
@@ -341,13 +632,19 @@ impl<'a> Value<'a> {
             Value::UInt64(val) | Value::QWord(_, val) => BigEndian::write_u64(buf, *val),
             Value::Int64(val) => BigEndian::write_i64(buf, *val),
             Value::Double(val) => BigEndian::write_f64(buf, *val),
+            Value::UInt128(val) => BigEndian::write_u128(buf, *val),
+            Value::Int128(val) => BigEndian::write_i128(buf, *val),
 
             Value::Text(val)
             | Value::DateTime(val)
             | Value::Date(val)
             | Value::Time(val)
             | Value::DecimalStr(val)
-            | Value::UserText(_, val) => buf[..val.len()].copy_from_slice(val.as_bytes()),
+            | Value::UserText(_, val) => {
+                buf[..val.len()].copy_from_slice(val.as_bytes());
+                // null terminator
+                buf[val.len()] = 0;
+            }
 
             Value::Blob(val) | Value::UserBlob(_, val) => buf[..val.len()].copy_from_slice(val),
 
@@ -397,6 +694,71 @@ impl<'a> TryFrom<Value<'a>> for Object<'a> {
     }
 }
 
+/// Indexes list by position or map by key
+///
+/// Returns null if value is neither a list nor a map or there is no such item
+impl Index<i32> for Value<'_> {
+    type Output = RawValue;
+
+    fn index(&self, index: i32) -> &RawValue {
+        match self {
+            Value::List(list) => usize::try_from(index).map_or(RawValue::NULL, |pos| &list[pos]),
+            Value::Map(map) => &map[index],
+            _ => RawValue::NULL,
+        }
+    }
+}
+
+/// Returns null if value is not an object or there is no item with given key
+impl Index<&str> for Value<'_> {
+    type Output = RawValue;
+
+    fn index(&self, key: &str) -> &RawValue {
+        match self {
+            Value::Object(obj) => &obj[key],
+            _ => RawValue::NULL,
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for UserContainer<'a> {
+    type Error = Value<'a>;
+
+    fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+        if let Value::UserContainer(container) = value {
+            Ok(container)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// Integers are converted by their numeric value, so any integer value
+/// that fits into requested type is accepted
+macro_rules! int_try_from_impl {
+    ($($int_type:ty),+) => {
+        $(
+            impl<'a> TryFrom<Value<'a>> for $int_type {
+                type Error = Value<'a>;
+
+                fn try_from(value: Value<'a>) -> core::result::Result<Self, Self::Error> {
+                    let converted = match value {
+                        Value::UInt128(v) => <$int_type>::try_from(v).ok(),
+                        Value::Int128(v) => <$int_type>::try_from(v).ok(),
+                        ref value => value
+                            .int_value()
+                            .and_then(|v| <$int_type>::try_from(v).ok()),
+                    };
+
+                    converted.ok_or(value)
+                }
+            }
+        )+
+    };
+}
+
+int_try_from_impl!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
 impl<'a> From<bool> for Value<'a> {
     fn from(value: bool) -> Self {
         if value {
@@ -427,5 +789,7 @@ value_from_impl!(f32, Float);
 value_from_impl!(u64, UInt64);
 value_from_impl!(i64, Int64);
 value_from_impl!(f64, Double);
+value_from_impl!(u128, UInt128);
+value_from_impl!(i128, Int128);
 value_from_impl!(&'a str, Text);
 value_from_impl!(&'a [u8], Blob);
@@ -0,0 +1,275 @@
+/*
+ * Generates interoperability fixtures of tests/main/data with reference
+ * implementation of binn (https://github.com/liteserver/binn).
+ *
+ * Each document is written as raw bytes to <dir>/<name>.bin (e.g.
+ * <dir>/list/large.bin) and compared with text fixtures by `c_fixtures`
+ * test. Use run.sh from this directory to build and run everything.
+ *
+ * Integers are added with functions of their own type, the library
+ * compresses them to the smallest type that can hold them.
+ */
+#include <math.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "binn.h"
+
+static const char *out_dir;
+static int failed;
+
+static void save(const char *name, binn *item)
+{
+    char path[512];
+    FILE *file;
+
+    snprintf(path, sizeof(path), "%s/%s.bin", out_dir, name);
+    file = fopen(path, "wb");
+    if (file == NULL || fwrite(binn_ptr(item), 1, binn_size(item), file) != (size_t)binn_size(item)) {
+        fprintf(stderr, "%s: can't write %s\n", name, path);
+        failed = 1;
+    }
+    if (file != NULL) {
+        fclose(file);
+    }
+    binn_free(item);
+}
+
+static void check(const char *name, int ok)
+{
+    if (!ok) {
+        fprintf(stderr, "%s: item was not added\n", name);
+        failed = 1;
+    }
+}
+
+/*
+ * Serializes list or object as user-defined container of given 2 byte type
+ *
+ * Library only creates lists, maps and objects, so header of serialized
+ * container is rewritten. Containers must be smaller than 128 bytes.
+ */
+static int user_container(binn *inner, int type, unsigned char *buf)
+{
+    unsigned char *src = binn_ptr(inner);
+    /* [type] [size] [count] of source all take 1 byte */
+    int data = binn_size(inner) - 3;
+
+    buf[0] = (unsigned char)(type >> 8);
+    buf[1] = (unsigned char)type;
+    buf[2] = (unsigned char)(data + 4);
+    buf[3] = (unsigned char)binn_count(inner);
+    memcpy(buf + 4, src + 3, data);
+    binn_free(inner);
+
+    return data + 4;
+}
+
+static void edge_values(void)
+{
+    const char *name = "list/edge_values";
+    binn *list = binn_list();
+    char text[129];
+    unsigned char blob[200];
+    int i;
+
+    memset(text, 'a', 128);
+    text[128] = 0;
+    for (i = 0; i < 200; i++) {
+        blob[i] = (unsigned char)i;
+    }
+
+    check(name, binn_list_add_uint8(list, UINT8_MAX));
+    check(name, binn_list_add_int8(list, INT8_MIN));
+    check(name, binn_list_add_uint16(list, UINT16_MAX));
+    check(name, binn_list_add_int16(list, INT16_MIN));
+    check(name, binn_list_add_uint32(list, UINT32_MAX));
+    check(name, binn_list_add_int32(list, INT32_MIN));
+    check(name, binn_list_add_uint64(list, UINT64_MAX));
+    check(name, binn_list_add_int64(list, INT64_MIN));
+    check(name, binn_list_add_float(list, -0.0f));
+    check(name, binn_list_add_double(list, INFINITY));
+    check(name, binn_list_add_str(list, ""));
+    check(name, binn_list_add_blob(list, blob, 0));
+    check(name, binn_list_add_str(list, text));
+    check(name, binn_list_add_blob(list, blob, 200));
+
+    save(name, list);
+}
+
+static void large(void)
+{
+    const char *name = "list/large";
+    binn *list = binn_list();
+    int i;
+
+    for (i = 0; i < 200; i++) {
+        check(name, binn_list_add_int32(list, i * 300 + 256));
+    }
+
+    save(name, list);
+}
+
+static void subtypes(void)
+{
+    const char *name = "map/subtypes";
+    binn *map = binn_map();
+    binn *child = binn_list();
+    binn *nested = binn_map();
+    binn *keyed = binn_object();
+    unsigned char byte = 0x3E;
+    unsigned char user[128];
+    int size;
+
+    check(name, binn_map_set(map, INT32_MIN, 0x1010, NULL, 0));
+    check(name, binn_map_set(map, INT32_MAX, 0x30FF, &byte, 0));
+    check(name, binn_map_set(map, 0, 0xBFFF, "Text", 0));
+
+    check(name, binn_list_add_uint8(child, 1));
+    check(name, binn_map_set_uint8(nested, 2, 2));
+    check(name, binn_list_add_map(child, nested));
+    binn_free(nested);
+    size = user_container(child, 0xF010, user);
+    check(name, binn_map_set(map, -1, 0xF010, user, size));
+
+    check(name, binn_object_set_null(keyed, "a"));
+    size = user_container(keyed, 0xFFFF, user);
+    check(name, binn_map_set(map, 1, 0xFFFF, user, size));
+
+    save(name, map);
+}
+
+static void compressed_ints(void)
+{
+    const char *name = "obj/compressed_ints";
+    binn *obj = binn_object();
+
+    check(name, binn_object_set_int32(obj, "a", 5));
+    check(name, binn_object_set_int64(obj, "b", -2));
+    check(name, binn_object_set_uint64(obj, "c", 300));
+    check(name, binn_object_set_int32(obj, "d", -40000));
+    check(name, binn_object_set_int64(obj, "e", 5000000000LL));
+    check(name, binn_object_set_int64(obj, "f", -5000000000LL));
+    check(name, binn_object_set_uint32(obj, "g", 70000));
+    check(name, binn_object_set_int16(obj, "h", -1));
+
+    save(name, obj);
+}
+
+static void nested(void)
+{
+    const char *name = "obj/nested";
+    binn *obj = binn_object();
+    binn *list = binn_list();
+    binn *map = binn_map();
+    binn *a, *b, *c;
+    unsigned char user[128];
+    unsigned char blob[130];
+    char text[101];
+    int i, size;
+
+    /* list: [{ map: { 1: [] } }, [], {}] */
+    a = binn_object();
+    b = binn_map();
+    c = binn_list();
+    check(name, binn_map_set_list(b, 1, c));
+    check(name, binn_object_set_map(a, "map", b));
+    check(name, binn_list_add_object(list, a));
+    check(name, binn_list_add_list(list, c));
+    binn_free(a);
+    binn_free(b);
+    binn_free(c);
+    b = binn_map();
+    check(name, binn_list_add_map(list, b));
+    binn_free(b);
+    check(name, binn_object_set_list(obj, "list", list));
+    binn_free(list);
+
+    /* map: { 7: { obj: {} }, 8: [[[true]]] } */
+    a = binn_object();
+    b = binn_object();
+    check(name, binn_object_set_object(a, "obj", b));
+    check(name, binn_map_set_object(map, 7, a));
+    binn_free(a);
+    binn_free(b);
+    a = binn_list();
+    b = binn_list();
+    c = binn_list();
+    check(name, binn_list_add_bool(c, TRUE));
+    check(name, binn_list_add_list(b, c));
+    check(name, binn_list_add_list(a, b));
+    check(name, binn_map_set_list(map, 8, a));
+    binn_free(a);
+    binn_free(b);
+    binn_free(c);
+    check(name, binn_object_set_map(obj, "map", map));
+    binn_free(map);
+
+    /* user: container of type 0xF014 with items {} and 1 */
+    a = binn_list();
+    b = binn_object();
+    check(name, binn_list_add_object(a, b));
+    check(name, binn_list_add_uint8(a, 1));
+    binn_free(b);
+    size = user_container(a, 0xF014, user);
+    check(name, binn_object_set(obj, "user", 0xF014, user, size));
+
+    /* big: [blob of 130 bytes, { text: "b" * 100 }] */
+    for (i = 0; i < 130; i++) {
+        blob[i] = (unsigned char)i;
+    }
+    memset(text, 'b', 100);
+    text[100] = 0;
+    a = binn_list();
+    b = binn_object();
+    check(name, binn_list_add_blob(a, blob, 130));
+    check(name, binn_object_set_str(b, "text", text));
+    check(name, binn_list_add_object(a, b));
+    check(name, binn_object_set_list(obj, "big", a));
+    binn_free(a);
+    binn_free(b);
+
+    save(name, obj);
+}
+
+static void unicode_keys(void)
+{
+    const char *name = "obj/unicode_keys";
+    binn *obj = binn_object();
+    char key[256];
+    int i;
+
+    /* 85 euro signs, 3 bytes each */
+    for (i = 0; i < 85; i++) {
+        memcpy(key + i * 3, "\xE2\x82\xAC", 3);
+    }
+    key[255] = 0;
+
+    check(name, binn_object_set_uint8(obj, "\xD0\xBA\xD0\xBB\xD1\x8E\xD1\x87", 1));
+    check(name, binn_object_set_uint8(obj, "\xE9\x94\xAE", 2));
+    check(name, binn_object_set_uint8(obj, "\xF0\x9F\x94\x91", 3));
+    check(name, binn_object_set_uint8(obj, "caf\xC3\xA9", 4));
+    check(name, binn_object_set_uint8(obj, "", 5));
+    check(name, binn_object_set_uint8(obj, key, 6));
+
+    save(name, obj);
+}
+
+int main(int argc, char **argv)
+{
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <output dir>\n", argv[0]);
+        return 2;
+    }
+    out_dir = argv[1];
+
+    edge_values();
+    large();
+    subtypes();
+    compressed_ints();
+    nested();
+    unicode_keys();
+
+    return failed;
+}
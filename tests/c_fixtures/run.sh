@@ -0,0 +1,14 @@
+#!/bin/sh
+# Generates fixtures with liteserver/binn and compares them with tests/main/data
+#
+# Usage (from repository root): tests/c_fixtures/run.sh <liteserver/binn checkout>
+set -eu
+
+BINN_DIR=$1
+OUT="$PWD/target/c_fixtures"
+
+echo "liteserver/binn $(git -C "$BINN_DIR" rev-parse HEAD)"
+mkdir -p "$OUT/list" "$OUT/map" "$OUT/obj"
+cc -Wall -I "$BINN_DIR/src" -o "$OUT/generate" tests/c_fixtures/generate.c "$BINN_DIR/src/binn.c" -lm
+"$OUT/generate" "$OUT"
+BINN_C_FIXTURES="$OUT" cargo test --test main c_fixtures -- --ignored
@@ -0,0 +1,69 @@
+use binn_rs::{List, Object, Value};
+
+#[test]
+fn serialize() {
+    let mut buf = vec![0; 128];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    list.add_value(0x0102030405060708090A0B0C0D0E0F10u128)
+        .unwrap();
+    list.add_value(-2i128).unwrap();
+
+    let expected = &[
+        0xE0, // [type] list (container)
+        0x29, // [size] container total size
+        0x02, // [count] items
+        0xD0, 0x80, // [type] = blob with subtype 128 (uint128)
+        0x10, // [size]
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // [data]
+        0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, //
+        0xD0, 0x81, // [type] = blob with subtype 129 (int128)
+        0x10, // [size]
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // [data]
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, //
+    ];
+
+    assert_eq!(list.as_bytes(), expected);
+}
+
+#[test]
+fn round_trip() {
+    let mut buf = vec![0; 128];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    obj.add_value("uuid", u128::MAX).unwrap();
+    obj.add_value("counter", i128::MIN).unwrap();
+
+    let obj: Object = Value::deserialize(obj.as_bytes())
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    assert_eq!(obj.get("uuid").unwrap(), Value::UInt128(u128::MAX));
+    assert_eq!(obj.get_as::<u128>("uuid"), Some(u128::MAX));
+    assert_eq!(obj.get_as::<i128>("counter"), Some(i128::MIN));
+    assert_eq!(obj.get_as::<i128>("uuid"), None);
+}
+
+#[test]
+fn wrong_length() {
+    // blob with subtype 128 that doesn't hold exactly 16 bytes is not
+    // an integer, so it's read as user blob
+    let bytes = &[0xD0, 0x80, 0x02, 0x01, 0x02];
+    let value = Value::deserialize(bytes).unwrap();
+    assert_eq!(
+        value,
+        Value::UserBlob(128.try_into().unwrap(), &[0x01, 0x02])
+    );
+    assert!(u128::try_from(value).is_err());
+
+    let bytes = &[0xD0, 0x81, 0x00];
+    let value = Value::deserialize(bytes).unwrap();
+    assert_eq!(value, Value::UserBlob(129.try_into().unwrap(), &[]));
+
+    // such values are written back unchanged
+    let mut buf = [0; 8];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    list.add_value(value).unwrap();
+    assert_eq!(&list.as_bytes()[3..], bytes);
+}
@@ -0,0 +1,21 @@
+//! Fixtures generated by reference implementation, see `tests/c_fixtures/run.sh`
+use crate::utils;
+
+const FIXTURES: [&str; 6] = [
+    "list/edge_values",
+    "list/large",
+    "map/subtypes",
+    "obj/compressed_ints",
+    "obj/nested",
+    "obj/unicode_keys",
+];
+
+#[test]
+#[ignore = "needs documents generated by liteserver/binn in BINN_C_FIXTURES"]
+fn reference_documents() {
+    let dir = std::env::var("BINN_C_FIXTURES").unwrap();
+    for name in FIXTURES {
+        let generated = std::fs::read(format!("{dir}/{name}.bin")).unwrap();
+        assert_eq!(utils::read_encoded_file(name), generated, "{name}");
+    }
+}
@@ -0,0 +1,186 @@
+use binn_rs::{binn, List, Map, Object, Value};
+
+#[test]
+fn sorted_object_keys() {
+    let mut buf = [0; 64];
+    let obj = binn!(buf.as_mut_slice(), {
+        "b": Value::UInt32(5),
+        "ab": Value::Int64(-2),
+        "a": Value::UInt64(300),
+    })
+    .unwrap();
+
+    let mut canonical_buf = [0; 64];
+    let canonical = obj.to_canonical(canonical_buf.as_mut_slice()).unwrap();
+
+    let expected = &[
+        0xE2, // [type] object (container)
+        0x11, // [size] container total size
+        0x03, // [count] items
+        0x01, b'a', // key
+        0x40, 0x01, 0x2C, // uint16
+        0x02, b'a', b'b', // key
+        0x21, 0xFE, // int8
+        0x01, b'b', // key
+        0x20, 0x05, // uint8
+    ];
+
+    assert_eq!(canonical.as_bytes(), expected);
+}
+
+#[test]
+fn sorted_map_keys() {
+    let mut buf = [0; 64];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+    map.add_value(3, Value::Int32(-200)).unwrap();
+    map.add_value(-1, Value::UInt16(1)).unwrap();
+    map.add_value(0, Value::Null).unwrap();
+
+    let mut canonical_buf = [0; 64];
+    let canonical = map.to_canonical(canonical_buf.as_mut_slice()).unwrap();
+
+    let expected = &[
+        0xE1, // [type] map (container)
+        0x15, // [size] container total size
+        0x03, // [count] items
+        0xFF, 0xFF, 0xFF, 0xFF, // key -1
+        0x20, 0x01, // uint8
+        0x00, 0x00, 0x00, 0x00, // key 0
+        0x00, // null
+        0x00, 0x00, 0x00, 0x03, // key 3
+        0x41, 0xFF, 0x38, // int16
+    ];
+
+    assert_eq!(canonical.as_bytes(), expected);
+}
+
+#[test]
+fn nested_containers() {
+    let mut buf = [0; 128];
+    let list = binn!(buf.as_mut_slice(), [
+        { "y": 1u32, "x": [2u64, -3i64] },
+        "text",
+    ])
+    .unwrap();
+
+    let mut canonical_buf = [0; 128];
+    let canonical = list.to_canonical(canonical_buf.as_mut_slice()).unwrap();
+
+    let mut expected_buf = [0; 128];
+    let expected = binn!(expected_buf.as_mut_slice(), [
+        { "x": [2u8, -3i8], "y": 1u8 },
+        "text",
+    ])
+    .unwrap();
+
+    assert_eq!(canonical.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn canonical_is_idempotent() {
+    let mut buf = [0; 128];
+    let obj = binn!(buf.as_mut_slice(), {
+        "z": { 5: 1u64, 2: [] },
+        "a": 70000u32,
+    })
+    .unwrap();
+
+    let mut first_buf = [0; 128];
+    let first = obj.to_canonical(first_buf.as_mut_slice()).unwrap();
+    let mut second_buf = [0; 128];
+    let second = first.to_canonical(second_buf.as_mut_slice()).unwrap();
+
+    assert_eq!(first.as_bytes(), second.as_bytes());
+}
+
+#[test]
+fn canonical_eq() {
+    let mut first_buf = [0; 128];
+    let first = binn!(first_buf.as_mut_slice(), {
+        "id": 5u64,
+        "tags": ["a", "b"],
+        "meta": { 1: -1i32, 2: true },
+    })
+    .unwrap();
+
+    let mut second_buf = [0; 128];
+    let second = binn!(second_buf.as_mut_slice(), {
+        "meta": { 2: true, 1: -1i8 },
+        "tags": ["a", "b"],
+        "id": 5u8,
+    })
+    .unwrap();
+
+    let mut third_buf = [0; 128];
+    let third = binn!(third_buf.as_mut_slice(), {
+        "meta": { 2: true, 1: -1i8 },
+        "tags": ["b", "a"],
+        "id": 5u8,
+    })
+    .unwrap();
+
+    let first = Value::from(first);
+    assert!(first.canonical_eq(&Value::from(second)));
+    assert!(!first.canonical_eq(&Value::from(third)));
+}
+
+#[test]
+fn canonical_eq_primitives() {
+    assert!(Value::UInt64(7).canonical_eq(&Value::Int8(7)));
+    assert!(!Value::UInt64(7).canonical_eq(&Value::Int8(-7)));
+    assert!(!Value::UInt8(1).canonical_eq(&Value::True));
+    assert!(Value::UInt8(1).canonical_eq(&Value::UInt128(1)));
+    assert!(Value::Int128(-300).canonical_eq(&Value::Int16(-300)));
+    assert!(Value::UInt128(u128::MAX).canonical_eq(&Value::UInt128(u128::MAX)));
+    assert!(!Value::UInt128(u128::MAX).canonical_eq(&Value::Int128(-1)));
+    assert!(Value::Text("a").canonical_eq(&Value::Text("a")));
+    assert!(!Value::from(List::empty()).canonical_eq(&Object::empty().into()));
+}
+
+#[test]
+fn int_width() {
+    let mut buf = [0; 128];
+    let list = binn!(
+        buf.as_mut_slice(),
+        [
+            Value::UInt128(5),
+            Value::Int128(-5),
+            Value::Int128(i64::MIN as i128 - 1),
+            Value::UInt128(u128::MAX),
+        ]
+    )
+    .unwrap();
+
+    let mut canonical_buf = [0; 128];
+    let canonical = list.to_canonical(canonical_buf.as_mut_slice()).unwrap();
+
+    let mut expected_buf = [0; 128];
+    let expected = binn!(
+        expected_buf.as_mut_slice(),
+        [
+            5u8,
+            -5i8,
+            Value::Int128(i64::MIN as i128 - 1),
+            Value::UInt128(u128::MAX),
+        ]
+    )
+    .unwrap();
+
+    assert_eq!(canonical.as_bytes(), expected.as_bytes());
+
+    // canonical and semantic equality agree on integers of any width
+    let values = [
+        Value::UInt8(5),
+        Value::Int64(5),
+        Value::UInt128(5),
+        Value::Int128(5),
+        Value::Int128(-5),
+        Value::UInt128(u128::MAX),
+        Value::Int128(i128::MIN),
+    ];
+    for a in &values {
+        for b in &values {
+            assert_eq!(a.canonical_eq(b), a.semantic_eq(b), "{a:?} {b:?}");
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use binn_rs::{List, Object, Value};
+
+#[test]
+fn compact_int() {
+    assert_eq!(Value::compact_int(0u64), Value::UInt8(0));
+    assert_eq!(Value::compact_int(255i32), Value::UInt8(255));
+    assert_eq!(Value::compact_int(256i64), Value::UInt16(256));
+    assert_eq!(Value::compact_int(70_000i64), Value::UInt32(70_000));
+    assert_eq!(Value::compact_int(-1i64), Value::Int8(-1));
+    assert_eq!(Value::compact_int(-129i64), Value::Int16(-129));
+    assert_eq!(Value::compact_int(i32::MIN), Value::Int32(i32::MIN));
+    assert_eq!(Value::compact_int(i64::MIN), Value::Int64(i64::MIN));
+    assert_eq!(
+        Value::compact_int(u64::MAX as i128 + 1),
+        Value::UInt128(u64::MAX as u128 + 1)
+    );
+    assert_eq!(Value::compact_int(i128::MIN), Value::Int128(i128::MIN));
+}
+
+#[test]
+fn compact_mode() {
+    let mut buf = [0; 64];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    assert!(!obj.compact_ints());
+
+    obj.set_compact_ints(true);
+    assert_eq!(obj.add_value("n", 5i64).unwrap(), Value::UInt8(5));
+    obj.add_value("m", -2i32).unwrap();
+    obj.add_value("f", 1.5f32).unwrap();
+
+    let expected = &[
+        0xE2, // [type] object (container)
+        0x12, // [size] container total size
+        0x03, // [count] items
+        0x01, b'n', // key
+        0x20, 0x05, // uint8
+        0x01, b'm', // key
+        0x21, 0xFE, // int8
+        0x01, b'f', // key
+        0x62, 0x3F, 0xC0, 0x00, 0x00, // float
+    ];
+
+    assert_eq!(obj.as_bytes(), expected);
+}
+
+#[test]
+fn nested_containers_inherit_mode() {
+    let mut buf = [0; 64];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    list.set_compact_ints(true);
+
+    let mut inner: List = list.add_value(List::empty()).unwrap().try_into().unwrap();
+    assert!(inner.compact_ints());
+    inner.add_value(1000u64).unwrap();
+
+    let expected = &[
+        0xE0, // [type] list (container)
+        0x09, // [size] container total size
+        0x01, // [count] items
+        0xE0, // [type] list (container)
+        0x06, // [size] container total size
+        0x01, // [count] items
+        0x40, 0x03, 0xE8, // uint16
+    ];
+
+    assert_eq!(list.as_bytes(), expected);
+}
+
+#[test]
+fn read_any_width() {
+    let mut buf = [0; 64];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    obj.set_compact_ints(true);
+    obj.add_value("small", 7u64).unwrap();
+    obj.add_value("negative", -7i64).unwrap();
+    obj.add_value("big", u64::MAX).unwrap();
+
+    assert_eq!(obj.get_as::<u64>("small"), Some(7));
+    assert_eq!(obj.get_as::<i64>("small"), Some(7));
+    assert_eq!(obj.get_as::<i64>("negative"), Some(-7));
+    assert_eq!(obj.get_as::<u64>("negative"), None);
+    assert_eq!(obj.get_as::<u64>("big"), Some(u64::MAX));
+    assert_eq!(obj.get_as::<i64>("big"), None);
+    assert_eq!(obj.get_as::<i128>("big"), Some(u64::MAX as i128));
+}
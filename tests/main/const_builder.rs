@@ -0,0 +1,106 @@
+use crate::utils;
+use binn_rs::{ConstList, ConstMap, ConstObject, List, Map, Object, Value};
+
+const PRIMITIVES: ConstObject<256> = ConstObject::new()
+    .add("v_null", Value::Null)
+    .add("v_true", Value::True)
+    .add("v_false", Value::False)
+    .add("n_u8", Value::UInt8(62))
+    .add("n_i8", Value::Int8(61))
+    .add("n_u16", Value::UInt16(6262))
+    .add("n_i16", Value::Int16(6161))
+    .add("n_u32", Value::UInt32(62626262))
+    .add("n_i32", Value::Int32(61616161))
+    .add("n_f32", Value::Float(0.6262))
+    .add("n_u64", Value::UInt64(6262626262626262))
+    .add("n_i64", Value::Int64(6161616161616161))
+    .add("n_f64", Value::Double(0.6161))
+    .add("s_text", Value::Text("Text"))
+    .add("s_datetime", Value::DateTime("DateTime"))
+    .add("s_date", Value::Date("Date"))
+    .add("s_time", Value::Time("Time"))
+    .add("s_decimal", Value::DecimalStr("Decimal"))
+    .add("b_blob", Value::Blob(&[0x62, 0x61, 0x62, 0x61]));
+
+static PRIMITIVES_BYTES: [u8; PRIMITIVES.len()] = PRIMITIVES.to_array();
+
+const CHILD_LIST: ConstList<16> = ConstList::new()
+    .add(Value::Null)
+    .add(Value::UInt8(62))
+    .add(Value::Int8(61));
+
+const CHILD_MAP: ConstMap<32> = ConstMap::new()
+    .add(-257978445, Value::Null)
+    .add(257978445, Value::UInt8(62))
+    .add(42, Value::Int8(61));
+
+const CHILD_OBJ: ConstObject<32> = ConstObject::new()
+    .add("v_null", Value::Null)
+    .add("n_u8", Value::UInt8(62))
+    .add("n_i8", Value::Int8(61));
+
+const CONTAINERS: ConstMap<128> = ConstMap::new()
+    .add_list(10, &CHILD_LIST)
+    .add_map(20, &CHILD_MAP)
+    .add_object(30, &CHILD_OBJ);
+
+#[test]
+fn primitives() {
+    let expected = utils::read_encoded_file("obj/primitives");
+
+    assert_eq!(PRIMITIVES.count(), 19);
+    assert_eq!(expected, PRIMITIVES_BYTES);
+}
+
+#[test]
+fn containers() {
+    let expected = utils::read_encoded_file("map/containers");
+    let actual: [u8; CONTAINERS.len()] = CONTAINERS.to_array();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn empty() {
+    const EMPTY: ConstList<0> = ConstList::new();
+
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.to_array::<3>(), [0xE0, 0x03, 0x00]);
+}
+
+#[test]
+#[should_panic]
+fn capacity_exceeded() {
+    ConstList::<4>::new().add(Value::Text("too long"));
+}
+
+static PRIMITIVES_OBJ: Object<'static> = Object::from_static(&PRIMITIVES_BYTES);
+
+const CONTAINERS_BYTES: [u8; CONTAINERS.len()] = CONTAINERS.to_array();
+const CONTAINERS_MAP: Map<'static> = Map::from_static(&CONTAINERS_BYTES);
+
+#[test]
+fn from_static() {
+    let expected = Object::from_bytes(&PRIMITIVES_BYTES).unwrap();
+    assert_eq!(PRIMITIVES_OBJ, expected);
+    assert_eq!(PRIMITIVES_OBJ.get("n_u16"), Some(Value::UInt16(6262)));
+
+    let list = CONTAINERS_MAP.get_as::<List>(10).unwrap();
+    assert_eq!(list.get(1), Some(Value::UInt8(62)));
+
+    const EMPTY: List<'static> = List::empty();
+    assert_eq!(EMPTY.count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "bytes start with container of other type")]
+fn from_static_type_mismatch() {
+    Object::from_static(&[0xE0, 0x03, 0x00]);
+}
+
+#[test]
+#[should_panic(expected = "container is malformed")]
+fn from_static_malformed() {
+    // nested list is longer than its parent
+    List::from_static(&[0xE0, 0x06, 0x01, 0xE0, 0x05, 0x00]);
+}
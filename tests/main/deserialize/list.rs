@@ -1,5 +1,5 @@
 use crate::utils;
-use binn_rs::{List, Map, Object, Value};
+use binn_rs::{Error, List, Map, Object, Value};
 
 #[test]
 fn primitives() {
@@ -75,7 +75,7 @@ fn containers() {
     assert_eq!(list.count(), 3);
 
     let mut iter = list.iter();
-    let child_expected = vec![
+    let child_expected = [
         (-257978445, "v_null", Value::Null),
         (257978445, "n_u8", Value::UInt8(62)),
         (42, "n_i8", Value::Int8(61)),
@@ -110,3 +110,84 @@ fn containers() {
 
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn large() {
+    let bytes = utils::read_encoded_file("list/large");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let list: List = value.try_into().unwrap();
+
+    assert_eq!(list.count(), 200);
+    assert_eq!(list.as_bytes().len(), bytes.len());
+
+    for (i, value) in list.iter().enumerate() {
+        assert_eq!(value, Value::UInt16(i as u16 * 300 + 256));
+    }
+}
+
+#[test]
+fn edge_values() {
+    let bytes = utils::read_encoded_file("list/edge_values");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let list: List = value.try_into().unwrap();
+
+    let text = "a".repeat(128);
+    let blob: Vec<u8> = (0..200).collect();
+    let expected = [
+        Value::UInt8(u8::MAX),
+        Value::Int8(i8::MIN),
+        Value::UInt16(u16::MAX),
+        Value::Int16(i16::MIN),
+        Value::UInt32(u32::MAX),
+        Value::Int32(i32::MIN),
+        Value::UInt64(u64::MAX),
+        Value::Int64(i64::MIN),
+        Value::Float(-0.0),
+        Value::Double(f64::INFINITY),
+        Value::Text(""),
+        Value::Blob(&[]),
+        Value::Text(&text),
+        Value::Blob(&blob),
+    ];
+
+    assert_eq!(expected.len(), list.count());
+
+    for (ref actual, expected) in list.iter().zip(expected.iter()) {
+        assert_eq!(actual, expected);
+    }
+
+    let Value::Float(zero) = list.get(8).unwrap() else {
+        panic!("expected float");
+    };
+    assert!(zero.is_sign_negative());
+}
+
+#[test]
+fn from_bytes() {
+    let bytes = utils::read_encoded_file("list/large");
+    let list = List::from_bytes(&bytes).unwrap();
+    assert_eq!(list.count(), 200);
+    assert_eq!(
+        list,
+        Value::deserialize(&bytes).unwrap().try_into().unwrap()
+    );
+
+    // trailing bytes are ignored
+    let bytes = [0xE0, 0x05, 0x02, 0x01, 0x00, 0xFF];
+    let list = List::from_bytes(&bytes).unwrap();
+    assert_eq!(list.as_bytes().len(), 5);
+
+    assert!(matches!(
+        List::from_bytes(&[0xE2, 0x03, 0x00]),
+        Err(Error::TypeMismatch)
+    ));
+    assert!(matches!(
+        List::from_bytes(&[0x20, 0x05]),
+        Err(Error::TypeMismatch)
+    ));
+    assert!(matches!(
+        List::from_bytes(&[0xE0, 0x05, 0x02, 0x01]),
+        Err(Error::Malformed)
+    ));
+    assert!(matches!(List::from_bytes(&[]), Err(Error::Malformed)));
+}
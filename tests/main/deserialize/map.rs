@@ -1,5 +1,5 @@
 use crate::utils;
-use binn_rs::{List, Map, Object, Value};
+use binn_rs::{Error, List, Map, Object, UserContainer, Value};
 
 #[test]
 fn primitives() {
@@ -90,7 +90,7 @@ fn containers() {
     assert_eq!(map.count(), 3);
 
     let mut iter = map.iter();
-    let child_expected = vec![
+    let child_expected = [
         (-257978445, "v_null", Value::Null),
         (257978445, "n_u8", Value::UInt8(62)),
         (42, "n_i8", Value::Int8(61)),
@@ -131,3 +131,42 @@ fn containers() {
 
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn subtypes() {
+    let bytes = utils::read_encoded_file("map/subtypes");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let map: Map = value.try_into().unwrap();
+
+    assert_eq!(map.count(), 5);
+    assert_eq!(map.get(i32::MIN).unwrap(), Value::Empty(16.into()));
+    assert_eq!(map.get(i32::MAX).unwrap(), Value::Byte(255.into(), 62));
+    assert_eq!(
+        map.get(0).unwrap(),
+        Value::UserText(4095.try_into().unwrap(), "Text")
+    );
+
+    let child: UserContainer = map.get(-1).unwrap().try_into().unwrap();
+    assert_eq!(child.subtype(), 16.into());
+    let child = child.into_list().unwrap();
+    assert_eq!(child.get(0).unwrap(), Value::UInt8(1));
+    let nested: Map = child.get(1).unwrap().try_into().unwrap();
+    assert_eq!(nested.get(2).unwrap(), Value::UInt8(2));
+
+    let child: UserContainer = map.get(1).unwrap().try_into().unwrap();
+    assert_eq!(child.subtype(), 4095.try_into().unwrap());
+    let child = child.into_object().unwrap();
+    assert_eq!(child.get("a").unwrap(), Value::Null);
+}
+
+#[test]
+fn from_bytes() {
+    let bytes = utils::read_encoded_file("map/primitives");
+    let map = Map::from_bytes(&bytes).unwrap();
+    assert_eq!(map, Value::deserialize(&bytes).unwrap().try_into().unwrap());
+
+    assert!(matches!(
+        Map::from_bytes(&[0xE0, 0x03, 0x00]),
+        Err(Error::TypeMismatch)
+    ));
+}
@@ -1,5 +1,5 @@
 use crate::utils;
-use binn_rs::{List, Map, Object, Value};
+use binn_rs::{Error, List, Map, Object, Value};
 
 #[test]
 fn primitives() {
@@ -90,7 +90,7 @@ fn containers() {
     assert_eq!(obj.count(), 3);
 
     let mut iter = obj.iter();
-    let child_expected = vec![
+    let child_expected = [
         (-257978445, "v_null", Value::Null),
         (257978445, "n_u8", Value::UInt8(62)),
         (42, "n_i8", Value::Int8(61)),
@@ -131,3 +131,95 @@ fn containers() {
 
     assert_eq!(iter.next(), None);
 }
+
+#[test]
+fn unicode_keys() {
+    let bytes = utils::read_encoded_file("obj/unicode_keys");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let obj: Object = value.try_into().unwrap();
+
+    let long_key = "€".repeat(85);
+    let expected = ["ключ", "键", "🔑", "café", "", long_key.as_str()];
+
+    assert_eq!(expected.len(), obj.count());
+
+    for (i, ((actual_key, actual_val), expected_key)) in obj.iter().zip(expected).enumerate() {
+        assert_eq!(actual_key, expected_key);
+        assert_eq!(actual_val, Value::UInt8(i as u8 + 1));
+    }
+
+    assert_eq!(obj.get(&long_key).unwrap(), Value::UInt8(6));
+}
+
+#[test]
+fn nested() {
+    let bytes = utils::read_encoded_file("obj/nested");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let obj: Object = value.try_into().unwrap();
+
+    assert_eq!(obj.count(), 4);
+
+    let list: List = obj.get("list").unwrap().try_into().unwrap();
+    let child: Object = list.get(0).unwrap().try_into().unwrap();
+    let child: Map = child.get("map").unwrap().try_into().unwrap();
+    let child: List = child.get(1).unwrap().try_into().unwrap();
+    assert_eq!(child.count(), 0);
+    assert_eq!(list.get(1).unwrap(), Value::List(List::empty()));
+    assert_eq!(list.get(2).unwrap(), Value::Map(Map::empty()));
+
+    let map: Map = obj.get("map").unwrap().try_into().unwrap();
+    let child: Object = map.get(7).unwrap().try_into().unwrap();
+    assert_eq!(child.get("obj").unwrap(), Value::Object(Object::empty()));
+    let child: List = map.get(8).unwrap().try_into().unwrap();
+    let child: List = child.get(0).unwrap().try_into().unwrap();
+    let child: List = child.get(0).unwrap().try_into().unwrap();
+    assert_eq!(child.get(0).unwrap(), Value::True);
+
+    let Value::UserContainer(user) = obj.get("user").unwrap() else {
+        panic!("expected user container");
+    };
+    assert_eq!(user.subtype(), 20.into());
+    let user = user.into_list().unwrap();
+    assert_eq!(user.get(0).unwrap(), Value::Object(Object::empty()));
+    assert_eq!(user.get(1).unwrap(), Value::UInt8(1));
+
+    let big: List = obj.get("big").unwrap().try_into().unwrap();
+    let blob: Vec<u8> = (0..130).collect();
+    assert_eq!(big.get(0).unwrap(), Value::Blob(&blob));
+    let child: Object = big.get(1).unwrap().try_into().unwrap();
+    assert_eq!(child.get("text").unwrap(), Value::Text(&"b".repeat(100)));
+}
+
+#[test]
+fn compressed_ints() {
+    let bytes = utils::read_encoded_file("obj/compressed_ints");
+    let value = Value::deserialize(bytes.as_slice()).unwrap();
+    let obj: Object = value.try_into().unwrap();
+
+    assert_eq!(obj.get_as::<i32>("a"), Some(5));
+    assert_eq!(obj.get_as::<i64>("b"), Some(-2));
+    assert_eq!(obj.get_as::<u64>("c"), Some(300));
+    assert_eq!(obj.get_as::<i32>("d"), Some(-40000));
+    assert_eq!(obj.get_as::<i64>("e"), Some(5000000000));
+    assert_eq!(obj.get_as::<i64>("f"), Some(-5000000000));
+    assert_eq!(obj.get_as::<u32>("g"), Some(70000));
+    assert_eq!(obj.get_as::<i16>("h"), Some(-1));
+}
+
+#[test]
+fn from_bytes() {
+    let bytes = utils::read_encoded_file("obj/nested");
+    let obj = Object::from_bytes(&bytes).unwrap();
+    assert_eq!(obj, Value::deserialize(&bytes).unwrap().try_into().unwrap());
+
+    assert!(matches!(
+        Object::from_bytes(&[0xE1, 0x03, 0x00]),
+        Err(Error::TypeMismatch)
+    ));
+
+    // objects of user-defined type can be read too
+    let bytes = [0xF0, 0x14, 0x0A, 0x01, 0x03, b'k', b'e', b'y', 0x20, 0x01];
+    let obj = Object::from_bytes(&bytes).unwrap();
+    assert_eq!(obj.subtype(), 20.into());
+    assert_eq!(obj.get("key"), Some(Value::UInt8(1)));
+}
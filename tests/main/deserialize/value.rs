@@ -0,0 +1,29 @@
+use crate::utils;
+use binn_rs::{Error, Storage, SubType, Value};
+
+#[test]
+fn peek_type() {
+    let bytes = utils::read_encoded_file("obj/nested");
+    assert_eq!(
+        Value::peek_type(&bytes).unwrap(),
+        (Storage::Container, SubType::new(2))
+    );
+
+    assert_eq!(
+        Value::peek_type(&[0x20]).unwrap(),
+        (Storage::Byte, SubType::new(0))
+    );
+    assert_eq!(
+        Value::peek_type(&[0xD0, 0x80]).unwrap(),
+        (Storage::Blob, SubType::new(128))
+    );
+
+    // value itself isn't read
+    assert_eq!(
+        Value::peek_type(&[0xA0, 0xFF]).unwrap(),
+        (Storage::String, SubType::new(0))
+    );
+
+    assert!(matches!(Value::peek_type(&[]), Err(Error::Malformed)));
+    assert!(matches!(Value::peek_type(&[0xF0]), Err(Error::Malformed)));
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "ffi")]
+
+use binn_rs::ffi::*;
+use binn_rs::{binn, List, Object, Value};
+use core::ffi::{c_char, c_int, c_void, CStr};
+use core::{ptr, slice};
+
+unsafe fn bytes<'a>(item: *mut c_void) -> &'a [u8] {
+    slice::from_raw_parts(binn_ptr(item) as *const u8, binn_size(item) as usize)
+}
+
+#[test]
+fn object() {
+    unsafe {
+        let list = binn_list();
+        assert_eq!(binn_list_add_int32(list, 300), 1);
+        assert_eq!(binn_list_add_str(list, c"text".as_ptr() as *mut c_char), 1);
+
+        let obj = binn_object();
+        assert_eq!(binn_object_set_int32(obj, c"id".as_ptr(), 5), 1);
+        assert_eq!(binn_object_set_int64(obj, c"neg".as_ptr(), -200), 1);
+        assert_eq!(binn_object_set_double(obj, c"ratio".as_ptr(), 0.5), 1);
+        assert_eq!(binn_object_set_bool(obj, c"ok".as_ptr(), 1), 1);
+        assert_eq!(binn_object_set_null(obj, c"none".as_ptr()), 1);
+        let blob = [1u8, 2, 3];
+        assert_eq!(
+            binn_object_set_blob(obj, c"raw".as_ptr(), blob.as_ptr() as *mut c_void, 3),
+            1
+        );
+        assert_eq!(
+            binn_object_set_list(obj, c"items".as_ptr(), list as *mut c_void),
+            1
+        );
+        binn_free(list);
+
+        let mut buf = [0; 128];
+        let expected = binn!(buf.as_mut_slice(), {
+            "id": 5u8,
+            "neg": -200i16,
+            "ratio": 0.5f64,
+            "ok": true,
+            "none": null,
+            "raw": Value::Blob(&[1, 2, 3]),
+            "items": [300u16, "text"],
+        })
+        .unwrap();
+
+        let item = obj as *mut c_void;
+        assert_eq!(bytes(item), expected.as_bytes());
+        assert_eq!(binn_type(item), BINN_OBJECT);
+        assert_eq!(binn_count(item), 7);
+
+        binn_free(obj);
+    }
+}
+
+#[test]
+fn read_serialized() {
+    let mut buf = [0; 128];
+    let obj = binn!(buf.as_mut_slice(), {
+        "id": 5u8,
+        "name": "binn",
+        "raw": Value::Blob(&[1, 2, 3]),
+        "items": [300u16, -1i8],
+        "ids": { 4: 1.5f32 },
+    })
+    .unwrap();
+    let ptr = obj.as_bytes().as_ptr() as *mut c_void;
+
+    unsafe {
+        assert_eq!(binn_size(ptr), obj.as_bytes().len() as c_int);
+        assert_eq!(binn_ptr(ptr), ptr);
+
+        let mut id = 0u64;
+        assert_eq!(binn_object_get_uint64(ptr, c"id".as_ptr(), &mut id), 1);
+        assert_eq!(id, 5);
+
+        let mut name = ptr::null_mut();
+        assert_eq!(binn_object_get_str(ptr, c"name".as_ptr(), &mut name), 1);
+        assert_eq!(CStr::from_ptr(name), c"binn");
+
+        let (mut raw, mut size) = (ptr::null_mut(), 0);
+        assert_eq!(
+            binn_object_get_blob(ptr, c"raw".as_ptr(), &mut raw, &mut size),
+            1
+        );
+        assert_eq!(
+            slice::from_raw_parts(raw as *const u8, size as usize),
+            &[1, 2, 3]
+        );
+
+        let mut items = ptr::null_mut();
+        assert_eq!(binn_object_get_list(ptr, c"items".as_ptr(), &mut items), 1);
+        assert_eq!(binn_type(items), BINN_LIST);
+        let mut value = 0i32;
+        assert_eq!(binn_list_get_int32(items, 1, &mut value), 1);
+        assert_eq!(value, 300);
+        assert_eq!(binn_list_get_int32(items, 2, &mut value), 1);
+        assert_eq!(value, -1);
+        assert_eq!(binn_list_get_int32(items, 0, &mut value), 0);
+        assert_eq!(binn_list_get_int32(items, 3, &mut value), 0);
+
+        let mut ids = ptr::null_mut();
+        assert_eq!(binn_object_get_map(ptr, c"ids".as_ptr(), &mut ids), 1);
+        let mut value = 0f64;
+        assert_eq!(binn_map_get_double(ids, 4, &mut value), 1);
+        assert_eq!(value, 1.5);
+    }
+}
+
+#[test]
+fn type_mismatch() {
+    unsafe {
+        let map = binn_map();
+        assert_eq!(binn_map_set_uint16(map, 1, 300), 1);
+        assert_eq!(binn_map_set_str(map, 2, ptr::null_mut()), 0);
+        assert_eq!(binn_object_set_int8(map, c"key".as_ptr(), 1), 0);
+        assert_eq!(binn_list_add_int8(map, 1), 0);
+
+        let item = map as *mut c_void;
+        let mut value = 0u8;
+        assert_eq!(binn_map_get_uint8(item, 1, &mut value), 0);
+        let mut text = ptr::null_mut();
+        assert_eq!(binn_map_get_str(item, 1, &mut text), 0);
+        assert_eq!(binn_map_get_str(item, 2, &mut text), 0);
+        assert_eq!(binn_object_get_str(item, c"key".as_ptr(), &mut text), 0);
+        assert_eq!(binn_count(item), 1);
+
+        binn_free(map);
+    }
+
+    assert_eq!(unsafe { binn_size(ptr::null_mut()) }, -1);
+}
+
+#[test]
+fn growth() {
+    unsafe {
+        let list = binn_list();
+        for i in 0..1000 {
+            assert_eq!(binn_list_add_uint32(list, i), 1);
+        }
+
+        let item = list as *mut c_void;
+        let list = List::try_from(Value::deserialize(bytes(item)).unwrap()).unwrap();
+        assert_eq!(list.count(), 1000);
+        assert_eq!(list.get(999), Some(Value::UInt16(999)));
+
+        let nested = binn_object();
+        assert_eq!(binn_object_set_list(nested, c"list".as_ptr(), item), 1);
+        let obj = Object::try_from(Value::deserialize(bytes(nested as *mut c_void)).unwrap());
+        assert_eq!(obj.unwrap().get("list"), Some(Value::List(list)));
+
+        binn_free(item as *mut Binn);
+        binn_free(nested);
+    }
+}
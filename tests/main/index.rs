@@ -0,0 +1,106 @@
+use crate::utils;
+use binn_rs::{List, Map, Object, RawValue, Value};
+
+#[test]
+fn list() {
+    let mut buf = vec![0; 512];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    list.add_value(Value::Null).unwrap();
+    list.add_value(Value::UInt16(6262)).unwrap();
+
+    assert_eq!(list[1], Value::UInt16(6262));
+    assert_eq!(list[1].as_bytes(), [0x40, 0x18, 0x76]);
+    assert_eq!(list.get_raw(0).unwrap().as_bytes(), [0x00]);
+    assert!(list[0].is_null());
+    assert!(list[2].is_null());
+    assert_eq!(list.get_raw(2), None);
+}
+
+#[test]
+fn map() {
+    let mut buf = vec![0; 512];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+
+    map.add_value(1, Value::True).unwrap();
+    map.add_value(-50, Value::UInt16(6262)).unwrap();
+
+    assert_eq!(map[1], Value::True);
+    assert_eq!(map[-50], Value::UInt16(6262));
+    assert!(map[10].is_null());
+    assert_eq!(map.get_raw(10), None);
+}
+
+#[test]
+fn obj() {
+    let mut buf = vec![0; 512];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    obj.add_value("v_null", Value::Null).unwrap();
+    obj.add_value("n_u16", Value::UInt16(6262)).unwrap();
+
+    assert!(obj["v_null"].is_null());
+    assert_eq!(obj.get_raw("v_null").unwrap().decode(), Value::Null);
+    assert_eq!(obj["n_u16"], Value::UInt16(6262));
+    assert!(obj["something"].is_null());
+    assert_eq!(obj.get_raw("something"), None);
+}
+
+#[test]
+fn nested() {
+    let bytes = utils::read_encoded_file("map/containers");
+    let map = Map::from_bytes(&bytes).unwrap();
+
+    assert_eq!(map[10][1], Value::UInt8(62));
+    assert_eq!(map[20][257978445], Value::UInt8(62));
+    assert_eq!(map[30]["n_i8"], Value::Int8(61));
+    assert!(map[30]["v_null"].is_null());
+
+    let value = Value::from(map);
+    assert_eq!(value[20][42], Value::Int8(61));
+    assert_eq!(value[30]["n_u8"].decode(), Value::UInt8(62));
+
+    // nested containers can be decoded from raw values
+    let child: Object = value[30].decode().try_into().unwrap();
+    assert_eq!(child.count(), 3);
+}
+
+#[test]
+fn wrong_kind() {
+    let bytes = utils::read_encoded_file("map/containers");
+    let value = Value::deserialize(&bytes).unwrap();
+
+    assert!(value[0].is_null());
+    assert!(value["key"].is_null());
+    assert!(value[10]["key"].is_null());
+    assert!(value[10][0][0].is_null());
+    assert!(value[20][0].is_null());
+    assert!(value[30][-257978445].is_null());
+    assert!(Value::UInt8(1)[0].is_null());
+}
+
+#[test]
+fn mutable_nested() {
+    let mut buf = [0; 64];
+    let mut root = List::empty_mut(buf.as_mut_slice()).unwrap();
+    root.add_object(|obj| {
+        obj.add_value("a", 1u8)?;
+        obj.add_list("b", |list| list.add_value("text").map(|_| ()))?;
+        assert_eq!(obj["b"][0], Value::Text("text"));
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(root[0]["a"], Value::UInt8(1));
+    assert_eq!(root[0]["b"][0], Value::Text("text"));
+}
+
+#[test]
+fn raw_value_from_bytes() {
+    let bytes = [0x40, 0x18, 0x76, 0xFF];
+    let raw = RawValue::from_bytes(&bytes).unwrap();
+    assert_eq!(raw.as_bytes(), &bytes[..3]);
+    assert_eq!(*raw, Value::UInt16(6262));
+
+    assert!(RawValue::from_bytes(&[0x40, 0x18]).is_err());
+}
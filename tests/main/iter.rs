@@ -0,0 +1,152 @@
+use crate::utils;
+use binn_rs::{apply_patch, binn, diff, List, Map, Object, Value};
+
+#[test]
+fn exact_size() {
+    let bytes = utils::read_encoded_file("list/primitives");
+    let list = List::from_bytes(&bytes).unwrap();
+
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), list.count());
+
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), list.count() - 2);
+    assert_eq!(iter.count(), list.count() - 2);
+}
+
+#[test]
+fn rev() {
+    let bytes = utils::read_encoded_file("list/primitives");
+    let list = List::from_bytes(&bytes).unwrap();
+    let mut expected: Vec<_> = list.iter().collect();
+    expected.reverse();
+    assert!(list.iter().rev().eq(expected));
+
+    let bytes = utils::read_encoded_file("map/primitives");
+    let map = Map::from_bytes(&bytes).unwrap();
+    let mut expected: Vec<_> = map.iter().collect();
+    expected.reverse();
+    assert!(map.iter().rev().eq(expected));
+
+    let bytes = utils::read_encoded_file("obj/containers");
+    let obj = Object::from_bytes(&bytes).unwrap();
+    let mut expected: Vec<_> = obj.iter().collect();
+    expected.reverse();
+    assert!(obj.iter().rev().eq(expected));
+}
+
+#[test]
+fn both_ends() {
+    let mut buf = [0; 64];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    for i in 0..5u8 {
+        list.add_value(i).unwrap();
+    }
+
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(Value::UInt8(0)));
+    assert_eq!(iter.next_back(), Some(Value::UInt8(4)));
+    assert_eq!(iter.next(), Some(Value::UInt8(1)));
+    assert_eq!(iter.next_back(), Some(Value::UInt8(3)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(Value::UInt8(2)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.len(), 0);
+}
+
+#[test]
+fn resume() {
+    let bytes = utils::read_encoded_file("obj/primitives");
+    let obj = Object::from_bytes(&bytes).unwrap();
+
+    let mut iter = obj.iter();
+    iter.nth(4);
+    let position = iter.current_position();
+    let expected: Vec<_> = iter.collect();
+
+    let resumed = obj.iter_from(position).unwrap();
+    assert_eq!(resumed.len(), expected.len());
+    assert!(resumed.eq(expected));
+
+    // position after the last item
+    let mut iter = obj.iter();
+    iter.by_ref().for_each(drop);
+    assert_eq!(obj.iter_from(iter.current_position()).unwrap().next(), None);
+}
+
+#[test]
+fn resume_after_growth() {
+    let mut buf = [0; 512];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+    map.add_value(1, 1u8).unwrap();
+    map.add_value(2, 2u8).unwrap();
+
+    let mut iter = map.iter();
+    iter.next();
+    let position = iter.current_position();
+
+    // header of map switches to full form
+    for i in 3..30 {
+        map.add_value(i, Value::UInt32(i as u32)).unwrap();
+    }
+    assert!(map.as_bytes().len() > 127);
+
+    let mut iter = map.iter_from(position).unwrap();
+    assert_eq!(iter.len(), 28);
+    assert_eq!(iter.next(), Some((2, Value::UInt8(2))));
+    assert_eq!(iter.next_back(), Some((29, Value::UInt32(29))));
+}
+
+#[test]
+fn invalid_position() {
+    let mut buf = [0; 64];
+    let mut long = List::empty_mut(buf.as_mut_slice()).unwrap();
+    for i in 0..5u8 {
+        long.add_value(i).unwrap();
+    }
+    let mut iter = long.iter();
+    iter.nth(3);
+    let position = iter.current_position();
+
+    let bytes = [0xE0, 0x05, 0x01, 0x20, 0x07];
+    let short = List::from_bytes(&bytes).unwrap();
+    assert!(short.iter_from(position).is_none());
+
+    // position after the last item is not at the end of longer list
+    let mut iter = short.iter();
+    iter.next();
+    let position = iter.current_position();
+    assert!(short.iter_from(position).is_some());
+    assert!(long.iter_from(position).is_some());
+}
+
+#[test]
+fn foreign_position() {
+    let bytes = [0xE0, 0x05, 0x02, 0x00, 0x00];
+    let nulls = List::from_bytes(&bytes).unwrap();
+    let mut iter = nulls.iter();
+    iter.next();
+    let position = iter.current_position();
+
+    // position points into the middle of blob
+    let bytes = [0xE0, 0x07, 0x02, 0xC0, 0x01, 0xFF, 0x00];
+    let blob = List::from_bytes(&bytes).unwrap();
+    assert!(blob.iter_from(position).is_none());
+
+    // stale position after item was replaced with shorter one
+    let mut buf = [0; 64];
+    let mut obj = binn!(buf.as_mut_slice(), { "a": "long text", "b": 1u8 }).unwrap();
+    let mut iter = obj.iter();
+    iter.next();
+    let position = iter.current_position();
+
+    let mut new_buf = [0; 64];
+    let new = binn!(new_buf.as_mut_slice(), { "a": 0u8, "b": 1u8 }).unwrap();
+    let mut patch_buf = [0; 64];
+    let old = Value::deserialize(obj.as_bytes()).unwrap();
+    let patch = diff(&old, &Value::from(new), patch_buf.as_mut_slice()).unwrap();
+    apply_patch(&mut obj, &patch).unwrap();
+    assert!(obj.iter_from(position).is_none());
+}
@@ -0,0 +1,111 @@
+use crate::utils;
+use binn_rs::{ConstList, List, Map, Object, RawValue, Storage, Value};
+
+#[test]
+fn keys() {
+    let bytes = utils::read_encoded_file("map/primitives");
+    let map = Map::from_bytes(&bytes).unwrap();
+    assert_eq!(map.keys().len(), map.count());
+    assert!(map.keys().eq(map.iter().map(|(key, _)| key)));
+    assert!(map.keys().rev().eq(map.iter().rev().map(|(key, _)| key)));
+
+    let bytes = utils::read_encoded_file("obj/containers");
+    let obj = Object::from_bytes(&bytes).unwrap();
+    assert!(obj.keys().eq(obj.iter().map(|(key, _)| key)));
+
+    let obj = Object::empty();
+    assert_eq!(obj.keys().next(), None);
+}
+
+#[test]
+fn iter_lazy() {
+    let bytes = utils::read_encoded_file("list/containers");
+    let list = List::from_bytes(&bytes).unwrap();
+    assert_eq!(list.iter_lazy().len(), list.count());
+    assert!(list.iter_lazy().map(RawValue::decode).eq(list.iter()));
+
+    let bytes = utils::read_encoded_file("map/user_types");
+    let map = Map::from_bytes(&bytes).unwrap();
+    let items = map.iter_lazy().rev().map(|(key, raw)| (key, raw.decode()));
+    assert!(items.eq(map.iter().rev()));
+
+    let bytes = utils::read_encoded_file("obj/primitives");
+    let obj = Object::from_bytes(&bytes).unwrap();
+    for (key, raw) in obj.iter_lazy() {
+        assert_eq!(*raw, obj.get(key).unwrap());
+        assert_eq!(raw, obj.get_raw(key).unwrap());
+    }
+}
+
+#[test]
+fn peek_type() {
+    let bytes = utils::read_encoded_file("map/subtypes");
+    let map = Map::from_bytes(&bytes).unwrap();
+
+    let raw = map.get_raw(-1).unwrap();
+    assert_eq!(raw.peek_type(), (Storage::Container, 16.into()));
+    assert_eq!(
+        map[0].peek_type(),
+        (Storage::String, 4095.try_into().unwrap())
+    );
+    assert_eq!(map[100].peek_type(), (Storage::NoBytes, 0.into()));
+}
+
+#[test]
+fn skip_items() {
+    let bytes = utils::read_encoded_file("obj/primitives");
+    let obj = Object::from_bytes(&bytes).unwrap();
+    let keys: Vec<_> = obj.keys().collect();
+
+    fn key<'a>(item: Option<(&'a str, Value<'_>)>) -> Option<&'a str> {
+        item.map(|(key, _)| key)
+    }
+
+    assert_eq!(key(obj.iter().nth(3)), Some(keys[3]));
+    assert_eq!(key(obj.iter().nth_back(3)), Some(keys[keys.len() - 4]));
+    assert_eq!(key(obj.iter().nth(keys.len())), None);
+
+    let mut iter = obj.iter();
+    assert_eq!(key(iter.nth(1)), Some(keys[1]));
+    assert_eq!(key(iter.nth_back(1)), Some(keys[keys.len() - 2]));
+    assert_eq!(iter.len(), keys.len() - 4);
+}
+
+#[test]
+fn text_size_boundary() {
+    // [size] of text doesn't include null terminator, so text with 127
+    // bytes still uses compact [size]
+    for len in 125..130 {
+        let text = "x".repeat(len);
+
+        let mut buf = [0; 512];
+        let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+        list.add_value(text.as_str()).unwrap();
+        list.add_value(1u8).unwrap();
+
+        let list = List::from_bytes(list.as_bytes()).unwrap();
+        assert_eq!(list.get(0), Some(Value::Text(&text)));
+        assert_eq!(list.get(1), Some(Value::UInt8(1)));
+
+        let raw = list.get_raw(0).unwrap();
+        assert_eq!(raw.as_bytes().len(), Value::Text(&text).encoded_len());
+        assert_eq!(RawValue::from_bytes(raw.as_bytes()).unwrap(), raw);
+    }
+
+    const TEXT: &str = "0123456789012345678901234567890123456789012345678901234567890123\
+                        012345678901234567890123456789012345678901234567890123456789012";
+    const LIST: ConstList<256> = ConstList::new().add(Value::Text(TEXT));
+    const BYTES: [u8; LIST.len()] = LIST.to_array();
+    assert_eq!(TEXT.len(), 127);
+    let list = List::from_bytes(&BYTES).unwrap();
+    assert_eq!(list.get(0), Some(Value::Text(TEXT)));
+}
+
+#[test]
+fn malformed_sizes() {
+    // container [size] smaller than its header
+    assert!(RawValue::from_bytes(&[0xE0, 0x02, 0x00]).is_err());
+    // text longer than given bytes
+    assert!(RawValue::from_bytes(&[0xA0, 0x05, b'a', 0x00]).is_err());
+    assert!(List::from_bytes(&[0xE0, 0x06, 0x01, 0xA0, 0x05, 0x00]).is_err());
+}
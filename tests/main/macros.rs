@@ -0,0 +1,102 @@
+use crate::utils;
+use binn_rs::{binn, Error, Map, Value};
+
+#[test]
+fn object() {
+    let expected = utils::read_encoded_file("obj/containers");
+
+    let mut buf = vec![0; 512];
+    let obj = binn!(buf.as_mut_slice(), {
+        "list": [null, 62u8, 61i8],
+        "map": {
+            -257978445: null,
+            257978445: 62u8,
+            42: 61i8,
+        },
+        "obj": {
+            "v_null": null,
+            "n_u8": 62u8,
+            "n_i8": 61i8
+        }
+    })
+    .unwrap();
+
+    assert_eq!(expected, obj.as_bytes());
+}
+
+#[test]
+fn list() {
+    let expected = utils::read_encoded_file("list/user_types");
+
+    let mut buf = vec![0; 512];
+    let list = binn!(
+        buf.as_mut_slice(),
+        [
+            Value::Empty(5.into()),
+            Value::Empty(20.into()),
+            Value::Byte(6.into(), 62),
+            Value::Byte(40.into(), 61),
+            Value::Word(7.into(), 6262),
+            Value::Word(80.into(), 6161),
+            Value::DWord(8.into(), 62626262),
+            Value::DWord(160.into(), 61616161),
+            Value::QWord(9.into(), 6262626262626262),
+            Value::QWord(320.try_into().unwrap(), 6161616161616161),
+            Value::UserText(10.into(), "Text"),
+            Value::UserText(645.try_into().unwrap(), "Date"),
+            Value::UserBlob(15.into(), &[0x62, 0x61, 0x62, 0x61]),
+            Value::UserBlob(4095.try_into().unwrap(), &[0x61, 0x62, 0x61, 0x62]),
+        ]
+    )
+    .unwrap();
+
+    assert_eq!(expected, list.as_bytes());
+}
+
+#[test]
+fn map() {
+    let key = 4;
+
+    let mut buf = vec![0; 64];
+    let map: Map = binn!(buf.as_mut_slice(), {
+        1: {},
+        2: [],
+        -3: true,
+        key: "text",
+        (key + 1): 6262u16
+    })
+    .unwrap();
+
+    let mut buf = vec![0; 64];
+    let mut expected = Map::empty_mut(buf.as_mut_slice()).unwrap();
+    expected.add_value(1, binn_rs::Object::empty()).unwrap();
+    expected.add_value(2, binn_rs::List::empty()).unwrap();
+    expected.add_value(-3, true).unwrap();
+    expected.add_value(4, "text").unwrap();
+    expected.add_value(5, 6262u16).unwrap();
+
+    assert_eq!(expected.as_bytes(), map.as_bytes());
+}
+
+#[test]
+fn empty() {
+    let mut buf = vec![0; 8];
+    let obj = binn!(buf.as_mut_slice(), {}).unwrap();
+    assert_eq!(obj.as_bytes(), &[0xE2, 0x03, 0x00]);
+
+    let mut buf = vec![0; 8];
+    let list = binn!(buf.as_mut_slice(), []).unwrap();
+    assert_eq!(list.as_bytes(), &[0xE0, 0x03, 0x00]);
+}
+
+#[test]
+fn small_buffer() {
+    let mut buf = vec![0; 16];
+    let res = binn!(buf.as_mut_slice(), {
+        "nested": {
+            "key": "some long text",
+        }
+    });
+
+    assert!(matches!(res, Err(Error::SmallBuffer(_))));
+}
@@ -1,4 +1,22 @@
+mod big_int;
+mod c_fixtures;
+mod canonical;
+mod compact_int;
+mod const_builder;
 mod deserialize;
+mod ffi;
 mod get_items;
+mod index;
+mod iter;
+mod lazy;
+mod macros;
+mod merge;
+mod nested;
+mod patch;
+mod raw_items;
+mod semantic;
 mod serialize;
+mod text_formats;
+mod user_container;
+mod user_type;
 mod utils;
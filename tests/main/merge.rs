@@ -0,0 +1,99 @@
+use binn_rs::{binn, ListMerge, Object};
+
+fn merge_with(base: &Object<'_>, overlay: &Object<'_>, lists: ListMerge, expected: &Object<'_>) {
+    let mut buf = [0; 256];
+    let merged = Object::merge_into_with(base, overlay, lists, buf.as_mut_slice()).unwrap();
+
+    assert_eq!(merged.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn nested() {
+    let mut base_buf = [0; 128];
+    let base = binn!(base_buf.as_mut_slice(), {
+        "interval": 10u16,
+        "wifi": { "ssid": "default", "retries": 3u8 },
+        "limits": { 1: 10u8, 2: { "max": 20u8 } },
+        "mode": "auto",
+    })
+    .unwrap();
+
+    let mut overlay_buf = [0; 128];
+    let overlay = binn!(overlay_buf.as_mut_slice(), {
+        "debug": true,
+        "wifi": { "ssid": "office" },
+        "limits": { 2: { "min": 5u8 }, 3: 30u8 },
+        "mode": { "manual": 1u8 },
+    })
+    .unwrap();
+
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), {
+        "interval": 10u16,
+        "wifi": { "ssid": "office", "retries": 3u8 },
+        "limits": { 1: 10u8, 2: { "max": 20u8, "min": 5u8 }, 3: 30u8 },
+        "mode": { "manual": 1u8 },
+        "debug": true,
+    })
+    .unwrap();
+
+    let mut buf = [0; 256];
+    let merged = Object::merge_into(&base, &overlay, buf.as_mut_slice()).unwrap();
+    assert_eq!(merged.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn lists() {
+    let mut base_buf = [0; 128];
+    let base = binn!(base_buf.as_mut_slice(), {
+        "tags": ["a", "b"],
+        "channels": [{ "id": 1u8, "gain": 2u8 }, { "id": 2u8 }],
+    })
+    .unwrap();
+
+    let mut overlay_buf = [0; 128];
+    let overlay = binn!(overlay_buf.as_mut_slice(), {
+        "tags": ["c"],
+        "channels": [{ "gain": 5u8 }, { "id": 3u8 }, { "id": 4u8 }],
+    })
+    .unwrap();
+
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), {
+        "tags": ["c"],
+        "channels": [{ "gain": 5u8 }, { "id": 3u8 }, { "id": 4u8 }],
+    })
+    .unwrap();
+    merge_with(&base, &overlay, ListMerge::Replace, &expected);
+
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), {
+        "tags": ["a", "b", "c"],
+        "channels": [
+            { "id": 1u8, "gain": 2u8 },
+            { "id": 2u8 },
+            { "gain": 5u8 },
+            { "id": 3u8 },
+            { "id": 4u8 },
+        ],
+    })
+    .unwrap();
+    merge_with(&base, &overlay, ListMerge::Append, &expected);
+
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), {
+        "tags": ["c", "b"],
+        "channels": [{ "id": 1u8, "gain": 5u8 }, { "id": 3u8 }, { "id": 4u8 }],
+    })
+    .unwrap();
+    merge_with(&base, &overlay, ListMerge::ByIndex, &expected);
+}
+
+#[test]
+fn empty() {
+    let mut base_buf = [0; 64];
+    let base = binn!(base_buf.as_mut_slice(), { "a": [1u8] }).unwrap();
+
+    merge_with(&base, &Object::empty(), ListMerge::Append, &base);
+    merge_with(&Object::empty(), &base, ListMerge::ByIndex, &base);
+}
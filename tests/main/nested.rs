@@ -0,0 +1,279 @@
+use binn_rs::{Error, List, Map, Object, Value};
+
+#[test]
+fn add_sequence() {
+    let mut buf = [0; 128];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    obj.add_value("id", 1u8).unwrap();
+
+    let mut list: List = obj
+        .add_value("list", List::empty())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    list.add_value(2u8).unwrap();
+
+    let mut map: Map = list.add_value(Map::empty()).unwrap().try_into().unwrap();
+    assert_eq!(map.add_value(3, "x").unwrap(), Value::Text("x"));
+
+    let mut inner: Object = map
+        .add_value(4, Object::empty())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    inner.add_value("t", true).unwrap();
+    assert_eq!(inner.get("t"), Some(Value::True));
+
+    // parents can be used again once nested container is not used anymore
+    map.add_value(5, 6u8).unwrap();
+    list.add_value(7u8).unwrap();
+    obj.add_value("end", Value::Null).unwrap();
+
+    let expected = [
+        0xE2, 0x34, 0x03, // object with 3 items
+        0x02, b'i', b'd', 0x20, 0x01, // "id": 1
+        0x04, b'l', b'i', b's', b't', // "list":
+        0xE0, 0x22, 0x03, 0x20, 0x02, // list [2,
+        0xE1, 0x1B, 0x03, // map {
+        0x00, 0x00, 0x00, 0x03, 0xA0, 0x01, b'x', 0x00, // 3: "x",
+        0x00, 0x00, 0x00, 0x04, // 4:
+        0xE2, 0x06, 0x01, 0x01, b't', 0x01, // {"t": true},
+        0x00, 0x00, 0x00, 0x05, 0x20, 0x06, // 5: 6},
+        0x20, 0x07, // 7]
+        0x03, b'e', b'n', b'd', 0x00, // "end": null
+    ];
+    assert_eq!(obj.as_bytes(), expected);
+}
+
+#[test]
+fn grow_all_headers() {
+    let mut buf = [0; 512];
+    let mut root = List::empty_mut(buf.as_mut_slice()).unwrap();
+    root.add_value(1u8).unwrap();
+
+    let mut child: List = root.add_value(List::empty()).unwrap().try_into().unwrap();
+    child.add_value(2u8).unwrap();
+
+    // headers of all three lists switch to full form
+    let mut grandchild: List = child.add_value(List::empty()).unwrap().try_into().unwrap();
+    for i in 0..130u8 {
+        grandchild.add_value(i).unwrap();
+    }
+    assert_eq!(grandchild.count(), 130);
+    assert_eq!(grandchild.get(129), Some(Value::UInt8(129)));
+
+    child.add_value(3u8).unwrap();
+    root.add_value(4u8).unwrap();
+
+    let root = List::from_bytes(root.as_bytes()).unwrap();
+    assert_eq!(root.count(), 3);
+    assert_eq!(root.get(2), Some(Value::UInt8(4)));
+
+    let child: List = root.get(1).unwrap().try_into().unwrap();
+    assert_eq!(child.count(), 3);
+    assert_eq!(child.get(0), Some(Value::UInt8(2)));
+    assert_eq!(child.get(2), Some(Value::UInt8(3)));
+
+    let grandchild: List = child.get(1).unwrap().try_into().unwrap();
+    assert!(grandchild.iter().map(u8::try_from).eq((0..130).map(Ok)));
+}
+
+/// Fills list nested into other list until buffer is full
+///
+/// Returns number of added items and error
+fn fill_nested(buf: &mut [u8]) -> (u8, Error) {
+    let mut root = List::empty_mut(buf).unwrap();
+    let mut child: List = root.add_value(List::empty()).unwrap().try_into().unwrap();
+
+    let mut count = 0;
+    loop {
+        match child.add_value(Value::UInt16(count.into())) {
+            Ok(_) => count += 1,
+            Err(err) => return (count, err),
+        }
+    }
+}
+
+#[test]
+fn small_buffer() {
+    // headers of both lists switch to full form when buffer is almost full
+    for size in 120..140 {
+        let mut buf = vec![0; size];
+        let (count, err) = fill_nested(&mut buf);
+        let Error::SmallBuffer(missing) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+
+        let root = List::from_bytes(&buf).unwrap();
+        let child: List = root.get(0).unwrap().try_into().unwrap();
+        assert!(child
+            .iter()
+            .map(u16::try_from)
+            .eq((0..count.into()).map(Ok)));
+
+        // reported size is enough for one more item
+        let mut buf = vec![0; size + missing];
+        assert_eq!(fill_nested(&mut buf).0, count + 1);
+    }
+}
+
+#[test]
+fn read_only() {
+    let bytes = [0xE0, 0x06, 0x01, 0xE0, 0x03, 0x00];
+    let root = List::from_bytes(&bytes).unwrap();
+    let mut child: List = root.get(0).unwrap().try_into().unwrap();
+
+    assert!(matches!(child.add_value(1u8), Err(Error::ReadOnly)));
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    List,
+    Map,
+    Object,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::List, Kind::Map, Kind::Object];
+
+    fn empty(self) -> Value<'static> {
+        match self {
+            Kind::List => List::empty().into(),
+            Kind::Map => Map::empty().into(),
+            Kind::Object => Object::empty().into(),
+        }
+    }
+
+    /// Encodes key of item at given position
+    fn key(self, pos: usize) -> Vec<u8> {
+        match self {
+            Kind::List => vec![],
+            Kind::Map => (pos as i32).to_be_bytes().to_vec(),
+            Kind::Object => vec![0x02, b'k', b'0' + pos as u8],
+        }
+    }
+
+    /// Encodes small container with given items
+    fn encode(self, items: &[Vec<u8>]) -> Vec<u8> {
+        let data: Vec<u8> = items
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, item)| [self.key(pos), item.clone()].concat())
+            .collect();
+        let data_type = match self {
+            Kind::List => 0xE0,
+            Kind::Map => 0xE1,
+            Kind::Object => 0xE2,
+        };
+
+        [
+            vec![data_type, data.len() as u8 + 3, items.len() as u8],
+            data,
+        ]
+        .concat()
+    }
+}
+
+/// Container of any kind used in test matrix
+#[derive(Debug)]
+enum Handle<'a> {
+    List(List<'a>),
+    Map(Map<'a>),
+    Object(Object<'a>),
+}
+
+impl<'a> Handle<'a> {
+    fn new(kind: Kind, buf: &'a mut [u8]) -> Self {
+        match kind {
+            Kind::List => Handle::List(List::empty_mut(buf).unwrap()),
+            Kind::Map => Handle::Map(Map::empty_mut(buf).unwrap()),
+            Kind::Object => Handle::Object(Object::empty_mut(buf).unwrap()),
+        }
+    }
+
+    fn add<'c, 'p: 'c>(&'p mut self, pos: usize, value: Value<'_>) -> Value<'c> {
+        match self {
+            Handle::List(list) => list.add_value(value),
+            Handle::Map(map) => map.add_value(pos as i32, value),
+            Handle::Object(obj) => obj.add_value(&format!("k{pos}"), value),
+        }
+        .unwrap()
+    }
+
+    fn add_nested(&mut self, pos: usize, kind: Kind) -> Handle<'_> {
+        match self.add(pos, kind.empty()) {
+            Value::List(list) => Handle::List(list),
+            Value::Map(map) => Handle::Map(map),
+            Value::Object(obj) => Handle::Object(obj),
+            value => panic!("unexpected value: {value:?}"),
+        }
+    }
+
+    fn get(&self, pos: usize) -> Option<Value<'_>> {
+        match self {
+            Handle::List(list) => list.get(pos),
+            Handle::Map(map) => map.get(pos as i32),
+            Handle::Object(obj) => obj.get(&format!("k{pos}")),
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Handle::List(list) => list.count(),
+            Handle::Map(map) => map.count(),
+            Handle::Object(obj) => obj.count(),
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Handle::List(list) => list.as_bytes(),
+            Handle::Map(map) => map.as_bytes(),
+            Handle::Object(obj) => obj.as_bytes(),
+        }
+    }
+}
+
+#[test]
+fn key_types_matrix() {
+    for root_kind in Kind::ALL {
+        for child_kind in Kind::ALL {
+            for grandchild_kind in Kind::ALL {
+                let kinds = (root_kind, child_kind, grandchild_kind);
+
+                let mut buf = [0; 128];
+                let mut root = Handle::new(root_kind, &mut buf);
+                root.add(0, Value::UInt8(1));
+
+                let mut child = root.add_nested(1, child_kind);
+                child.add(0, Value::UInt8(2));
+
+                let mut grandchild = child.add_nested(1, grandchild_kind);
+                grandchild.add(0, Value::UInt8(3));
+                grandchild.add(1, Value::UInt8(4));
+                assert_eq!(grandchild.get(1), Some(Value::UInt8(4)), "{kinds:?}");
+                assert_eq!(grandchild.count(), 2, "{kinds:?}");
+
+                let grandchild_bytes =
+                    grandchild_kind.encode(&[vec![0x20, 0x03], vec![0x20, 0x04]]);
+                assert_eq!(grandchild.as_bytes(), grandchild_bytes, "{kinds:?}");
+
+                child.add(2, Value::UInt8(5));
+                assert_eq!(child.get(0), Some(Value::UInt8(2)), "{kinds:?}");
+                assert_eq!(child.get(2), Some(Value::UInt8(5)), "{kinds:?}");
+
+                let child_bytes =
+                    child_kind.encode(&[vec![0x20, 0x02], grandchild_bytes, vec![0x20, 0x05]]);
+                assert_eq!(child.as_bytes(), child_bytes, "{kinds:?}");
+
+                root.add(2, Value::UInt8(6));
+                assert_eq!(root.get(2), Some(Value::UInt8(6)), "{kinds:?}");
+
+                let root_bytes =
+                    root_kind.encode(&[vec![0x20, 0x01], child_bytes, vec![0x20, 0x06]]);
+                assert_eq!(root.as_bytes(), root_bytes, "{kinds:?}");
+                assert_eq!(root.count(), 3, "{kinds:?}");
+            }
+        }
+    }
+}
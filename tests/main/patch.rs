@@ -0,0 +1,283 @@
+use binn_rs::{apply_patch, binn, diff, Error, List, Object, Value};
+
+/// Copies `base` into given buffer and applies patch there
+fn patched<'a>(
+    base: &Object<'_>,
+    patch: &List<'_>,
+    buf: &'a mut [u8],
+) -> Result<Object<'a>, Error> {
+    let len = base.as_bytes().len();
+    buf[..len].copy_from_slice(base.as_bytes());
+
+    let mut patched = Object::open_mut(buf)?;
+    apply_patch(&mut patched, patch)?;
+
+    Ok(patched)
+}
+
+fn round_trip(old: &Object<'_>, new: &Object<'_>) {
+    let old_value = Value::deserialize(old.as_bytes()).unwrap();
+    let new_value = Value::deserialize(new.as_bytes()).unwrap();
+
+    let mut patch_buf = [0; 512];
+    let patch = diff(&old_value, &new_value, patch_buf.as_mut_slice()).unwrap();
+
+    let mut patched_buf = [0; 512];
+    let patched = patched(old, &patch, patched_buf.as_mut_slice()).unwrap();
+
+    assert!(Value::from(patched).semantic_eq(&new_value));
+}
+
+#[test]
+fn operations() {
+    let mut old_buf = [0; 128];
+    let old = binn!(old_buf.as_mut_slice(), {
+        "name": "sensor",
+        "limits": { 1: 10u8, 2: 20u8 },
+        "debug": true,
+    })
+    .unwrap();
+
+    let mut new_buf = [0; 128];
+    let new = binn!(new_buf.as_mut_slice(), {
+        "name": "sensor",
+        "limits": { 1: 10u8, 2: 25u8 },
+        "tags": [],
+    })
+    .unwrap();
+
+    let old = Value::deserialize(old.as_bytes()).unwrap();
+    let new = Value::deserialize(new.as_bytes()).unwrap();
+
+    let mut patch_buf = [0; 256];
+    let patch = diff(&old, &new, patch_buf.as_mut_slice()).unwrap();
+
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), [
+        { "op": "replace", "path": ["limits", 2i32], "value": 25u8 },
+        { "op": "remove", "path": ["debug"] },
+        { "op": "add", "path": ["tags"], "value": List::empty() },
+    ])
+    .unwrap();
+
+    assert_eq!(patch.as_bytes(), expected.as_bytes());
+}
+
+#[test]
+fn nested_changes() {
+    let mut old_buf = [0; 128];
+    let old = binn!(old_buf.as_mut_slice(), {
+        "interval": 10u16,
+        "channels": [{ "id": 1u8 }, { "id": 2u8 }, { "id": 3u8 }],
+        "calibration": { -1: 0.5f32, 7: "x" },
+        "mode": "auto",
+    })
+    .unwrap();
+
+    let mut new_buf = [0; 128];
+    let new = binn!(new_buf.as_mut_slice(), {
+        "interval": 60u32,
+        "channels": [{ "id": 1u8, "gain": 2u8 }, { "id": 5u8 }],
+        "calibration": { 7: "y", 8: null },
+        "mode": { "manual": true },
+    })
+    .unwrap();
+
+    round_trip(&old, &new);
+    round_trip(&new, &old);
+}
+
+#[test]
+fn list_growth() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "values": [1u8] }).unwrap();
+
+    let mut new_buf = [0; 64];
+    let new = binn!(new_buf.as_mut_slice(), { "values": [1u8, 2u8, [3u8]] }).unwrap();
+
+    round_trip(&old, &new);
+    round_trip(&new, &old);
+}
+
+#[test]
+fn list_removal_order() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "values": [1u8, 2u8, 3u8, 4u8] }).unwrap();
+
+    let mut new_buf = [0; 64];
+    let new = binn!(new_buf.as_mut_slice(), { "values": [1u8] }).unwrap();
+
+    let mut patch_buf = [0; 256];
+    let patch = diff(
+        &Value::deserialize(old.as_bytes()).unwrap(),
+        &Value::deserialize(new.as_bytes()).unwrap(),
+        patch_buf.as_mut_slice(),
+    )
+    .unwrap();
+
+    // operations are applied in order, so items are removed from the end
+    let mut expected_buf = [0; 256];
+    let expected = binn!(expected_buf.as_mut_slice(), [
+        { "op": "remove", "path": ["values", 3u32] },
+        { "op": "remove", "path": ["values", 2u32] },
+        { "op": "remove", "path": ["values", 1u32] },
+    ])
+    .unwrap();
+    assert_eq!(patch.as_bytes(), expected.as_bytes());
+
+    let mut patched_buf = [0; 64];
+    let patched = patched(&old, &patch, patched_buf.as_mut_slice()).unwrap();
+    assert_eq!(patched.as_bytes(), new.as_bytes());
+}
+
+#[test]
+fn header_form_changes() {
+    let text = "x".repeat(150);
+
+    let mut old_buf = [0; 512];
+    let old = binn!(old_buf.as_mut_slice(), {
+        "first": { "a": [1u8], "b": 2u8 },
+        "last": 3u8,
+    })
+    .unwrap();
+
+    let mut new_buf = [0; 512];
+    let new = binn!(new_buf.as_mut_slice(), {
+        "first": { "a": [1u8, text.as_str()], "b": 2u8 },
+        "last": 3u8,
+    })
+    .unwrap();
+
+    let old_value = Value::deserialize(old.as_bytes()).unwrap();
+    let new_value = Value::deserialize(new.as_bytes()).unwrap();
+    let mut patched_buf = [0; 512];
+
+    // nested containers in the middle of document switch to full headers and back
+    let mut patch_buf = [0; 512];
+    let patch = diff(&old_value, &new_value, patch_buf.as_mut_slice()).unwrap();
+    let grown = patched(&old, &patch, patched_buf.as_mut_slice()).unwrap();
+    assert_eq!(grown.as_bytes(), new.as_bytes());
+
+    let patch = diff(&new_value, &old_value, patch_buf.as_mut_slice()).unwrap();
+    let shrunk = patched(&new, &patch, patched_buf.as_mut_slice()).unwrap();
+    assert_eq!(shrunk.as_bytes(), old.as_bytes());
+}
+
+#[test]
+fn small_buffer() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "a": 1u8 }).unwrap();
+
+    let mut patch_buf = [0; 64];
+    let patch = binn!(patch_buf.as_mut_slice(), [
+        { "op": "replace", "path": ["a"], "value": "text" },
+    ])
+    .unwrap();
+
+    let mut patched_buf = [0; 10];
+    let res = patched(&old, &patch, patched_buf.as_mut_slice());
+    assert!(matches!(res, Err(Error::SmallBuffer(2))));
+}
+
+#[test]
+fn equal_documents() {
+    let mut buf = [0; 64];
+    let obj = binn!(buf.as_mut_slice(), { "a": [1u8, { "b": null }] }).unwrap();
+    let value = Value::deserialize(obj.as_bytes()).unwrap();
+
+    let mut patch_buf = [0; 64];
+    let patch = diff(&value, &value, patch_buf.as_mut_slice()).unwrap();
+    assert_eq!(patch.count(), 0);
+
+    let mut patched_buf = [0; 64];
+    let patched = patched(&obj, &patch, patched_buf.as_mut_slice()).unwrap();
+    assert_eq!(patched.as_bytes(), obj.as_bytes());
+}
+
+#[test]
+fn replace_root() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "a": 1u8 }).unwrap();
+
+    let mut patch_buf = [0; 64];
+    let patch = diff(
+        &Value::deserialize(old.as_bytes()).unwrap(),
+        &Value::Null,
+        patch_buf.as_mut_slice(),
+    )
+    .unwrap();
+
+    let mut expected_buf = [0; 64];
+    let expected = binn!(expected_buf.as_mut_slice(), [
+        { "op": "replace", "path": [], "value": null },
+    ])
+    .unwrap();
+    assert_eq!(patch.as_bytes(), expected.as_bytes());
+
+    // only object can replace root object
+    let mut patched_buf = [0; 64];
+    let res = patched(&old, &patch, patched_buf.as_mut_slice());
+    assert!(matches!(res, Err(Error::PatchConflict)));
+
+    let mut patch_buf = [0; 64];
+    let patch = binn!(patch_buf.as_mut_slice(), [
+        { "op": "replace", "path": [], "value": { "b": 2u8 } },
+    ])
+    .unwrap();
+
+    let patched = patched(&old, &patch, patched_buf.as_mut_slice()).unwrap();
+    assert_eq!(patched.get("b").unwrap(), Value::UInt8(2));
+    assert_eq!(patched.count(), 1);
+}
+
+fn apply_error(base: &Object<'_>, patch: &List<'_>) -> Error {
+    let mut patched_buf = [0; 64];
+    patched(base, patch, patched_buf.as_mut_slice()).unwrap_err()
+}
+
+#[test]
+fn conflicts() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "a": 1u8, "list": [1u8] }).unwrap();
+    let mut patch_buf = [0; 64];
+
+    let patch = binn!(patch_buf.as_mut_slice(), [{ "op": "remove", "path": ["b"] }]).unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::PatchConflict));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [
+        { "op": "add", "path": ["a"], "value": 2u8 },
+    ])
+    .unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::PatchConflict));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [
+        { "op": "add", "path": ["list", 5u32], "value": 2u8 },
+    ])
+    .unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::PatchConflict));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [
+        { "op": "replace", "path": ["a", "b"], "value": 2u8 },
+    ])
+    .unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::PatchConflict));
+}
+
+#[test]
+fn malformed_patch() {
+    let mut old_buf = [0; 64];
+    let old = binn!(old_buf.as_mut_slice(), { "a": 1u8 }).unwrap();
+    let mut patch_buf = [0; 64];
+
+    let patch = binn!(patch_buf.as_mut_slice(), [1u8]).unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::Malformed));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [{ "op": "move", "path": ["a"] }]).unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::Malformed));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [{ "op": "add", "path": ["b"] }]).unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::Malformed));
+
+    let patch = binn!(patch_buf.as_mut_slice(), [{ "op": "remove", "path": "a" }]).unwrap();
+    assert!(matches!(apply_error(&old, &patch), Error::Malformed));
+}
@@ -0,0 +1,105 @@
+use crate::utils;
+use binn_rs::{Error, List, Map, Object, Value};
+
+#[test]
+fn list_offsets() {
+    let bytes = utils::read_encoded_file("list/primitives");
+    let list = List::from_bytes(&bytes).unwrap();
+
+    assert_eq!(list.iter_raw().len(), list.count());
+    for ((offset, raw, value), expected) in list.iter_raw().zip(list.iter()) {
+        assert_eq!(&list.as_bytes()[offset..offset + raw.len()], raw);
+        assert_eq!(Value::deserialize(raw).unwrap(), value);
+        assert_eq!(value, expected);
+    }
+
+    let (offset, raw, value) = list.iter_raw().next().unwrap();
+    assert_eq!((offset, raw, value), (3, &[0x00][..], Value::Null));
+}
+
+#[test]
+fn map_offsets() {
+    let bytes = [
+        0xE1, 0x0E, 0x02, // map with 2 items
+        0x00, 0x00, 0x00, 0x01, 0x20, 0x05, // 1: 5
+        0xFF, 0xFF, 0xFF, 0xFF, 0x01, // -1: true
+    ];
+    let map = Map::from_bytes(&bytes).unwrap();
+
+    let items: Vec<_> = map.iter_raw().collect();
+    assert_eq!(
+        items,
+        [
+            (1, 7, &[0x20, 0x05][..], Value::UInt8(5)),
+            (-1, 13, &[0x01][..], Value::True),
+        ]
+    );
+
+    let items: Vec<_> = map.iter_raw().rev().map(|(key, ..)| key).collect();
+    assert_eq!(items, [-1, 1]);
+}
+
+#[test]
+fn copy_items() {
+    let bytes = utils::read_encoded_file("obj/containers");
+    let src = Object::from_bytes(&bytes).unwrap();
+
+    let mut buf = [0; 512];
+    let mut dst = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    for (key, offset, raw, _) in src.iter_raw() {
+        assert_eq!(&bytes[offset..offset + raw.len()], raw);
+        dst.add_raw(key, raw).unwrap();
+    }
+    assert_eq!(dst.as_bytes(), bytes);
+
+    // items can be copied between containers of different kinds
+    let mut buf = [0; 512];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    for (_, _, raw, _) in src.iter_raw().rev() {
+        list.add_raw(raw).unwrap();
+    }
+    let expected: Vec<_> = src.iter().rev().map(|(_, value)| value).collect();
+    assert!(list.iter().eq(expected));
+}
+
+#[test]
+fn add_raw_nested() {
+    let mut buf = [0; 64];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+    map.add_list(1, |list| {
+        list.add_raw(&[0x20, 0x07])?;
+        list.add_raw(&[0xE2, 0x03, 0x00])
+    })
+    .unwrap();
+
+    let list: List = map.get(1).unwrap().try_into().unwrap();
+    assert_eq!(list.get(0), Some(Value::UInt8(7)));
+    assert_eq!(list.get(1), Some(Object::empty().into()));
+}
+
+#[test]
+fn add_raw_errors() {
+    let mut buf = [0; 8];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    // bytes after value
+    assert!(matches!(
+        list.add_raw(&[0x20, 0x07, 0x00]),
+        Err(Error::Malformed)
+    ));
+    // incomplete value
+    assert!(list.add_raw(&[0x40, 0x07]).is_err());
+    assert!(list.add_raw(&[]).is_err());
+    // malformed nested container
+    assert!(list.add_raw(&[0xE0, 0x04, 0x01, 0xFF]).is_err());
+    assert_eq!(list.count(), 0);
+
+    assert!(matches!(
+        list.add_raw(&[0xA0, 0x04, b't', b'e', b'x', b't', 0x00]),
+        Err(Error::SmallBuffer(2))
+    ));
+
+    let bytes = [0xE0, 0x03, 0x00];
+    let mut list = List::from_bytes(&bytes).unwrap();
+    assert!(matches!(list.add_raw(&[0x00]), Err(Error::ReadOnly)));
+}
@@ -0,0 +1,74 @@
+use binn_rs::{binn, SemanticValue, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+fn hash(value: Value<'_>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    SemanticValue(value).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn numbers() {
+    assert!(Value::UInt8(5).semantic_eq(&Value::Int32(5)));
+    assert!(Value::UInt128(5).semantic_eq(&Value::Int8(5)));
+    assert!(Value::Int128(-1).semantic_eq(&Value::Int64(-1)));
+    assert!(!Value::UInt64(u64::MAX).semantic_eq(&Value::Int64(-1)));
+    assert!(Value::Float(1.5).semantic_eq(&Value::Double(1.5)));
+    assert!(Value::Double(f64::NAN).semantic_eq(&Value::Double(f64::NAN)));
+    assert!(!Value::Double(0.0).semantic_eq(&Value::Double(-0.0)));
+    assert!(!Value::UInt8(1).semantic_eq(&Value::Double(1.0)));
+    assert!(!Value::UInt8(1).semantic_eq(&Value::True));
+
+    assert_eq!(hash(Value::UInt8(5)), hash(Value::Int32(5)));
+    assert_eq!(hash(Value::UInt128(5)), hash(Value::Int64(5)));
+    assert_eq!(hash(Value::Float(1.5)), hash(Value::Double(1.5)));
+}
+
+#[test]
+fn other_values() {
+    assert!(Value::Text("a").semantic_eq(&Value::Text("a")));
+    assert!(!Value::Text("a").semantic_eq(&Value::Date("a")));
+    assert!(Value::Blob(&[1, 2]).semantic_eq(&Value::Blob(&[1, 2])));
+    assert!(Value::Null.semantic_eq(&Value::Null));
+}
+
+#[test]
+fn containers() {
+    let mut first_buf = [0; 128];
+    let first = binn!(first_buf.as_mut_slice(), {
+        "id": 5u64,
+        "tags": ["a", 2i32],
+        "meta": { 1: -1i32, 2: 0.5f64 },
+    })
+    .unwrap();
+
+    let mut second_buf = [0; 128];
+    let second = binn!(second_buf.as_mut_slice(), {
+        "meta": { 2: 0.5f32, 1: -1i8 },
+        "id": 5u8,
+        "tags": ["a", 2u128],
+    })
+    .unwrap();
+
+    let mut third_buf = [0; 128];
+    let third = binn!(third_buf.as_mut_slice(), {
+        "meta": { 2: 0.5f32, 1: -1i8 },
+        "id": 5u8,
+        "tags": [2u128, "a"],
+    })
+    .unwrap();
+
+    let (first, second, third) = (Value::from(first), Value::from(second), Value::from(third));
+
+    assert_ne!(first, second);
+    assert!(first.semantic_eq(&second));
+    assert!(!first.semantic_eq(&third));
+
+    let mut map = HashMap::new();
+    map.insert(SemanticValue(first), "cached");
+
+    assert_eq!(map.get(&SemanticValue(second)), Some(&"cached"));
+    assert_eq!(map.get(&SemanticValue(third)), None);
+}
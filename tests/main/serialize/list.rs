@@ -1,5 +1,5 @@
 use crate::utils;
-use binn_rs::{List, Map, Object, Value};
+use binn_rs::{Error, List, Map, Object, Value};
 
 #[test]
 fn primitives() {
@@ -37,6 +37,24 @@ fn primitives() {
     assert_eq!(expected, list.as_bytes());
 }
 
+#[test]
+fn text_terminator() {
+    // null terminator must be written even if buffer is not zeroed
+    let mut buf = [0xFF; 32];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    list.add_value("text").unwrap();
+    list.add_value(Value::UserText(10.into(), "user")).unwrap();
+
+    assert_eq!(
+        list.as_bytes(),
+        [
+            0xE0, 0x11, 0x02, // list with 2 items
+            0xA0, 0x04, b't', b'e', b'x', b't', 0x00, // text
+            0xAA, 0x04, b'u', b's', b'e', b'r', 0x00, // user text
+        ]
+    );
+}
+
 #[test]
 fn user_types() {
     let expected = utils::read_encoded_file("list/user_types");
@@ -103,3 +121,130 @@ fn containers() {
 
     assert_eq!(expected, list.as_bytes());
 }
+
+#[test]
+fn nested_header_growth() {
+    let mut buf = vec![0; 512];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    list.add_value(1u8).unwrap();
+
+    // headers of both lists switch to full form while child is in use
+    let mut child: List = list.add_value(List::empty()).unwrap().try_into().unwrap();
+    for i in 0..100u16 {
+        child.add_value(i).unwrap();
+    }
+    child.add_value("last").unwrap();
+
+    let list: List = Value::deserialize(list.as_bytes())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(list.get(0).unwrap(), Value::UInt8(1));
+
+    let child: List = list.get(1).unwrap().try_into().unwrap();
+    assert_eq!(child.count(), 101);
+    for i in 0..100u16 {
+        assert_eq!(child.get(i as usize).unwrap(), Value::UInt16(i));
+    }
+    assert_eq!(child.get(100).unwrap(), Value::Text("last"));
+}
+
+#[test]
+fn large() {
+    let expected = utils::read_encoded_file("list/large");
+
+    let mut buf = vec![0; 1024];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    for i in 0..200u16 {
+        list.add_value(i * 300 + 256).unwrap();
+    }
+
+    assert_eq!(expected, list.as_bytes());
+}
+
+#[test]
+fn edge_values() {
+    let expected = utils::read_encoded_file("list/edge_values");
+
+    // buffer is not zeroed, so all bytes (including null terminators) must be written
+    let mut buf = vec![0xFF; 512];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    list.add_value(u8::MAX).unwrap();
+    list.add_value(i8::MIN).unwrap();
+    list.add_value(u16::MAX).unwrap();
+    list.add_value(i16::MIN).unwrap();
+    list.add_value(u32::MAX).unwrap();
+    list.add_value(i32::MIN).unwrap();
+    list.add_value(u64::MAX).unwrap();
+    list.add_value(i64::MIN).unwrap();
+    list.add_value(-0.0f32).unwrap();
+    list.add_value(f64::INFINITY).unwrap();
+    list.add_value("").unwrap();
+    list.add_value([].as_slice()).unwrap();
+
+    let text = "a".repeat(128);
+    list.add_value(text.as_str()).unwrap();
+    let blob: Vec<u8> = (0..200).collect();
+    list.add_value(blob.as_slice()).unwrap();
+
+    assert_eq!(expected, list.as_bytes());
+}
+
+#[test]
+fn open_mut() {
+    let expected = utils::read_encoded_file("list/large");
+
+    let mut buf = vec![0; 1024];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+    for i in 0..10u16 {
+        list.add_value(i * 300 + 256).unwrap();
+    }
+
+    // header switches to full form after list is opened
+    let mut list = List::open_mut(buf.as_mut_slice()).unwrap();
+    assert_eq!(list.count(), 10);
+    for i in 10..200u16 {
+        list.add_value(i * 300 + 256).unwrap();
+    }
+
+    assert_eq!(expected, list.as_bytes());
+
+    // only bytes of list are available
+    let len = expected.len();
+    let mut list = List::open_mut(&mut buf[..len]).unwrap();
+    assert!(matches!(list.add_value(1u8), Err(Error::SmallBuffer(2))));
+}
+
+#[test]
+fn containers_with_closures() {
+    let expected = utils::read_encoded_file("list/containers");
+
+    let mut buf = vec![0; 512];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    list.add_list(|child| {
+        child.add_value(Value::Null)?;
+        child.add_value(62u8)?;
+        child.add_value(61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    list.add_map(|child| {
+        child.add_value(-257978445, Value::Null)?;
+        child.add_value(257978445, 62u8)?;
+        child.add_value(42, 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    list.add_object(|child| {
+        child.add_value("v_null", Value::Null)?;
+        child.add_value("n_u8", 62u8)?;
+        child.add_value("n_i8", 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(expected, list.as_bytes());
+}
@@ -123,3 +123,85 @@ fn containers() {
 
     assert_eq!(expected, map.as_bytes());
 }
+
+#[test]
+fn subtypes() {
+    let expected = utils::read_encoded_file("map/subtypes");
+
+    let mut buf = vec![0; 512];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+
+    map.add_value(i32::MIN, Value::Empty(16.into())).unwrap();
+    map.add_value(i32::MAX, Value::Byte(255.into(), 62))
+        .unwrap();
+    map.add_value(0, Value::UserText(4095.try_into().unwrap(), "Text"))
+        .unwrap();
+
+    let mut child_buf = vec![0; 64];
+    let mut child = List::empty_mut_with_subtype(16.into(), child_buf.as_mut_slice()).unwrap();
+    child.add_value(1u8).unwrap();
+    let mut nested: Map = child.add_value(Map::empty()).unwrap().try_into().unwrap();
+    nested.add_value(2, 2u8).unwrap();
+    map.add_value(-1, child).unwrap();
+
+    let mut child_buf = vec![0; 64];
+    let mut child =
+        Object::empty_mut_with_subtype(4095.try_into().unwrap(), child_buf.as_mut_slice()).unwrap();
+    child.add_value("a", Value::Null).unwrap();
+    map.add_value(1, child).unwrap();
+
+    assert_eq!(expected, map.as_bytes());
+}
+
+#[test]
+fn open_mut() {
+    let encoded = utils::read_encoded_file("map/primitives");
+
+    let mut buf = vec![0; 512];
+    buf[..encoded.len()].copy_from_slice(&encoded);
+
+    let mut map = Map::open_mut(buf.as_mut_slice()).unwrap();
+    let count = map.count();
+    map.add_value(-1, "appended").unwrap();
+
+    let map: Map = Value::deserialize(&buf).unwrap().try_into().unwrap();
+    assert_eq!(map.count(), count + 1);
+    assert_eq!(map.get(-1).unwrap(), Value::Text("appended"));
+
+    let original: Map = Value::deserialize(&encoded).unwrap().try_into().unwrap();
+    for (key, value) in original.iter() {
+        assert_eq!(map.get(key).unwrap(), value);
+    }
+}
+
+#[test]
+fn containers_with_closures() {
+    let expected = utils::read_encoded_file("map/containers");
+
+    let mut buf = vec![0; 512];
+    let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+
+    map.add_list(10, |child| {
+        child.add_value(Value::Null)?;
+        child.add_value(62u8)?;
+        child.add_value(61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    map.add_map(20, |child| {
+        child.add_value(-257978445, Value::Null)?;
+        child.add_value(257978445, 62u8)?;
+        child.add_value(42, 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    map.add_object(30, |child| {
+        child.add_value("v_null", Value::Null)?;
+        child.add_value("n_u8", 62u8)?;
+        child.add_value("n_i8", 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(expected, map.as_bytes());
+}
@@ -1,3 +1,4 @@
 mod list;
 mod map;
 mod obj;
+mod value;
@@ -1,5 +1,5 @@
 use crate::utils;
-use binn_rs::{List, Map, Object, Value};
+use binn_rs::{binn, Error, List, Map, Object, Value};
 
 #[test]
 fn primitives() {
@@ -40,6 +40,23 @@ fn primitives() {
     assert_eq!(expected, obj.as_bytes());
 }
 
+#[test]
+fn long_key() {
+    let key = "k".repeat(255);
+
+    let mut buf = [0; 512];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    obj.add_value(&key, 1u8).unwrap();
+    assert!(obj.add_value(&"k".repeat(256), 1u8).is_err());
+
+    let bytes = obj.as_bytes();
+    assert_eq!(bytes[6], 255);
+    assert_eq!(&bytes[7..262], key.as_bytes());
+
+    let obj: Object = Value::deserialize(bytes).unwrap().try_into().unwrap();
+    assert_eq!(obj.get(&key), Some(Value::UInt8(1)));
+}
+
 #[test]
 fn user_types() {
     let expected = utils::read_encoded_file("obj/user_types");
@@ -128,3 +145,143 @@ fn containers() {
 
     assert_eq!(expected, obj.as_bytes());
 }
+
+#[test]
+fn unicode_keys() {
+    let expected = utils::read_encoded_file("obj/unicode_keys");
+
+    let mut buf = vec![0; 512];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    obj.add_value("ключ", 1u8).unwrap();
+    obj.add_value("键", 2u8).unwrap();
+    obj.add_value("🔑", 3u8).unwrap();
+    obj.add_value("café", 4u8).unwrap();
+    obj.add_value("", 5u8).unwrap();
+    obj.add_value("€".repeat(85).as_str(), 6u8).unwrap();
+
+    assert_eq!(expected, obj.as_bytes());
+}
+
+#[test]
+fn nested() {
+    let expected = utils::read_encoded_file("obj/nested");
+
+    let mut user_buf = vec![0; 64];
+    let mut user = List::empty_mut_with_subtype(20.into(), user_buf.as_mut_slice()).unwrap();
+    user.add_value(Object::empty()).unwrap();
+    user.add_value(1u8).unwrap();
+
+    let blob: Vec<u8> = (0..130).collect();
+    let text = "b".repeat(100);
+
+    let mut buf = vec![0; 512];
+    let obj = binn!(buf.as_mut_slice(), {
+        "list": [{ "map": { 1: [] } }, [], Map::empty()],
+        "map": { 7: { "obj": {} }, 8: [[[true]]] },
+        "user": user,
+        "big": [blob.as_slice(), { "text": text.as_str() }],
+    })
+    .unwrap();
+
+    assert_eq!(expected, obj.as_bytes());
+}
+
+#[test]
+fn compressed_ints() {
+    let expected = utils::read_encoded_file("obj/compressed_ints");
+
+    let mut buf = vec![0; 512];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+    obj.set_compact_ints(true);
+
+    obj.add_value("a", 5i32).unwrap();
+    obj.add_value("b", -2i64).unwrap();
+    obj.add_value("c", 300u64).unwrap();
+    obj.add_value("d", -40000i32).unwrap();
+    obj.add_value("e", 5000000000i64).unwrap();
+    obj.add_value("f", -5000000000i64).unwrap();
+    obj.add_value("g", 70000u32).unwrap();
+    obj.add_value("h", -1i16).unwrap();
+
+    assert_eq!(expected, obj.as_bytes());
+}
+
+#[test]
+fn open_mut() {
+    let mut buf = [0; 32];
+    List::empty_mut(buf.as_mut_slice()).unwrap();
+    assert!(matches!(
+        Object::open_mut(buf.as_mut_slice()),
+        Err(Error::TypeMismatch)
+    ));
+
+    let mut buf = [0xE2, 0x05, 0x02, 0x00, 0x00];
+    assert!(matches!(
+        Object::open_mut(buf.as_mut_slice()),
+        Err(Error::Malformed)
+    ));
+
+    // objects of user-defined type can be opened too
+    let mut buf = [0; 32];
+    Object::empty_mut_with_subtype(20.into(), buf.as_mut_slice()).unwrap();
+    let mut obj = Object::open_mut(buf.as_mut_slice()).unwrap();
+    obj.add_value("key", 1u8).unwrap();
+
+    let expected = [0xF0, 0x14, 0x0A, 0x01, 0x03, b'k', b'e', b'y', 0x20, 0x01];
+    assert_eq!(obj.as_bytes(), expected);
+    assert_eq!(obj.subtype(), 20.into());
+}
+
+#[test]
+fn containers_with_closures() {
+    let expected = utils::read_encoded_file("obj/containers");
+
+    let mut buf = vec![0; 512];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    obj.add_list("list", |child| {
+        child.add_value(Value::Null)?;
+        child.add_value(62u8)?;
+        child.add_value(61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    obj.add_map("map", |child| {
+        child.add_value(-257978445, Value::Null)?;
+        child.add_value(257978445, 62u8)?;
+        child.add_value(42, 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+    obj.add_object("obj", |child| {
+        child.add_value("v_null", Value::Null)?;
+        child.add_value("n_u8", 62u8)?;
+        child.add_value("n_i8", 61i8)?;
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(expected, obj.as_bytes());
+}
+
+#[test]
+fn closure_errors() {
+    let mut buf = [0; 16];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    // items added before error are kept
+    let result = obj.add_list("l", |list| {
+        list.add_value(1u8)?;
+        list.add_value("too long")?;
+        Ok(())
+    });
+    assert!(matches!(result, Err(Error::SmallBuffer(_))));
+
+    let list: List = obj.get("l").unwrap().try_into().unwrap();
+    assert_eq!(list.count(), 1);
+
+    let result = obj.add_object("o", |_| Err(Error::Malformed));
+    assert!(matches!(result, Err(Error::Malformed)));
+    assert_eq!(obj.count(), 2);
+}
@@ -0,0 +1,61 @@
+use binn_rs::{Error, List, SubType, Value};
+
+#[test]
+fn scalars() {
+    let values: [(Value, &[u8]); 8] = [
+        (Value::Null, &[0x00]),
+        (Value::True, &[0x01]),
+        (Value::Int16(-2), &[0x41, 0xFF, 0xFE]),
+        (Value::UInt32(62626262), &[0x60, 0x03, 0xBB, 0x99, 0xD6]),
+        (Value::Text("ab"), &[0xA0, 0x02, b'a', b'b', 0x00]),
+        (Value::Blob(&[1, 2, 3]), &[0xC0, 0x03, 0x01, 0x02, 0x03]),
+        (Value::Word(SubType::new(300), 5), &[0x51, 0x2C, 0x00, 0x05]),
+        (
+            Value::UInt128(1),
+            &[
+                0xD0, 0x80, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            ],
+        ),
+    ];
+
+    for (value, expected) in values {
+        let mut buf = [0xFF; 32];
+        assert_eq!(value.encoded_len(), expected.len());
+        assert_eq!(value.serialize_into(&mut buf).unwrap(), expected.len());
+        assert_eq!(&buf[..expected.len()], expected);
+        assert_eq!(Value::deserialize(&buf[..expected.len()]).unwrap(), value);
+    }
+}
+
+#[test]
+fn containers() {
+    let mut list_buf = [0; 16];
+    let mut list = List::empty_mut(list_buf.as_mut_slice()).unwrap();
+    list.add_value(1u8).unwrap();
+
+    let value = Value::from(list);
+    let mut buf = [0; 16];
+    assert_eq!(value.encoded_len(), 5);
+    assert_eq!(value.serialize_into(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], &[0xE0, 0x05, 0x01, 0x20, 0x01]);
+}
+
+#[test]
+fn small_buffer() {
+    let mut buf = [0; 4];
+    let res = Value::Text("text").serialize_into(&mut buf);
+    assert!(matches!(res, Err(Error::SmallBuffer(3))));
+
+    let res = Value::UInt64(1).serialize_into(&mut buf);
+    assert!(matches!(res, Err(Error::SmallBuffer(5))));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn to_vec() {
+    assert_eq!(Value::Blob(&[1, 2]).to_vec(), [0xC0, 0x02, 0x01, 0x02]);
+    assert_eq!(
+        Value::Double(0.5).to_vec(),
+        [0x82, 0x3F, 0xE0, 0, 0, 0, 0, 0, 0]
+    );
+}
@@ -0,0 +1,161 @@
+#[cfg(feature = "chrono")]
+mod chrono {
+    use binn_rs::{Object, TextValue, Value};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    #[test]
+    fn write() {
+        let mut buf = vec![0; 256];
+        let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2023, 4, 5).unwrap();
+        let time = NaiveTime::from_hms_milli_opt(7, 8, 9, 120).unwrap();
+
+        obj.add_value("datetime", &TextValue::from(date.and_time(time)))
+            .unwrap();
+        obj.add_value("date", &TextValue::from(date)).unwrap();
+        obj.add_value("time", &TextValue::from(time)).unwrap();
+        obj.add_value(
+            "far",
+            &TextValue::from(NaiveDate::from_ymd_opt(-44, 3, 15).unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            obj.get("datetime").unwrap(),
+            Value::DateTime("2023-04-05 07:08:09.12")
+        );
+        assert_eq!(obj.get("date").unwrap(), Value::Date("2023-04-05"));
+        assert_eq!(obj.get("time").unwrap(), Value::Time("07:08:09.12"));
+        assert_eq!(obj.get("far").unwrap(), Value::Date("-0044-03-15"));
+    }
+
+    #[test]
+    fn read() {
+        let expected = NaiveDate::from_ymd_opt(2023, 4, 5)
+            .unwrap()
+            .and_hms_nano_opt(7, 8, 9, 5)
+            .unwrap();
+
+        let actual: NaiveDateTime = Value::DateTime("2023-04-05T07:08:09.000000005")
+            .try_into()
+            .unwrap();
+        assert_eq!(actual, expected);
+
+        let actual: NaiveDateTime = Value::DateTime("2023-04-05 07:08:09.000000005")
+            .try_into()
+            .unwrap();
+        assert_eq!(actual, expected);
+
+        let leap: NaiveTime = Value::Time("23:59:60.5").try_into().unwrap();
+        assert_eq!(
+            leap,
+            NaiveTime::from_hms_milli_opt(23, 59, 59, 1500).unwrap()
+        );
+        assert_eq!(TextValue::from(leap).as_str(), "23:59:60.5");
+    }
+
+    #[test]
+    fn invalid() {
+        let res: Result<NaiveDate, _> = Value::Date("2023-02-30").try_into();
+        assert_eq!(res, Err(Value::Date("2023-02-30")));
+
+        let res: Result<NaiveDate, _> = Value::Date("2023-2-3").try_into();
+        assert_eq!(res, Err(Value::Date("2023-2-3")));
+
+        let res: Result<NaiveTime, _> = Value::Time("07:08:09.").try_into();
+        assert_eq!(res, Err(Value::Time("07:08:09.")));
+
+        let res: Result<NaiveDate, _> = Value::Text("2023-04-05").try_into();
+        assert_eq!(res, Err(Value::Text("2023-04-05")));
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use binn_rs::{List, TextValue, Value};
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    #[test]
+    fn round_trip() {
+        let mut buf = vec![0; 256];
+        let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+        let date = Date::from_calendar_date(-1, Month::December, 31).unwrap();
+        let time = Time::from_hms_micro(23, 0, 1, 10).unwrap();
+        let datetime = PrimitiveDateTime::new(date, time);
+
+        list.add_value(&TextValue::from(datetime)).unwrap();
+        list.add_value(&TextValue::from(date)).unwrap();
+        list.add_value(&TextValue::from(time)).unwrap();
+
+        assert_eq!(
+            list.get(0).unwrap(),
+            Value::DateTime("-0001-12-31 23:00:01.00001")
+        );
+        assert_eq!(list.get_as::<PrimitiveDateTime>(0), Some(datetime));
+        assert_eq!(list.get_as::<Date>(1), Some(date));
+        assert_eq!(list.get_as::<Time>(2), Some(time));
+    }
+
+    #[test]
+    fn invalid() {
+        let res: Result<Time, _> = Value::Time("23:59:60").try_into();
+        assert_eq!(res, Err(Value::Time("23:59:60")));
+
+        let res: Result<Time, _> = Value::Time("24:00:00").try_into();
+        assert_eq!(res, Err(Value::Time("24:00:00")));
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod decimal {
+    use binn_rs::{Map, TextValue, Value};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = vec![0; 256];
+        let mut map = Map::empty_mut(buf.as_mut_slice()).unwrap();
+
+        let price = Decimal::new(-123456, 3);
+        map.add_value(1, &TextValue::from(price)).unwrap();
+        map.add_value(2, &TextValue::from(Decimal::MAX)).unwrap();
+
+        assert_eq!(map.get(1).unwrap(), Value::DecimalStr("-123.456"));
+        assert_eq!(map.get_as::<Decimal>(1), Some(price));
+        assert_eq!(map.get_as::<Decimal>(2), Some(Decimal::MAX));
+    }
+
+    #[test]
+    fn invalid() {
+        let res: Result<Decimal, _> = Value::DecimalStr("12.3.4").try_into();
+        assert_eq!(res, Err(Value::DecimalStr("12.3.4")));
+
+        let res: Result<Decimal, _> = Value::Text("12.3").try_into();
+        assert_eq!(res, Err(Value::Text("12.3")));
+    }
+}
+
+#[test]
+fn free_form_text() {
+    use binn_rs::{List, Value};
+
+    let mut buf = [0; 128];
+    let mut list = List::empty_mut(buf.as_mut_slice()).unwrap();
+
+    // binn doesn't specify format of these values, so any text is written
+    list.add_value(Value::DateTime("2023-04-05T07:08:09Z"))
+        .unwrap();
+    list.add_value(Value::Date("April 5")).unwrap();
+    list.add_value(Value::Time("7:08")).unwrap();
+    list.add_value(Value::DecimalStr("1e5")).unwrap();
+
+    let expected = [
+        Value::DateTime("2023-04-05T07:08:09Z"),
+        Value::Date("April 5"),
+        Value::Time("7:08"),
+        Value::DecimalStr("1e5"),
+    ];
+    assert_eq!(list.iter().collect::<Vec<_>>(), expected);
+}
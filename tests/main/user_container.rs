@@ -0,0 +1,129 @@
+use binn_rs::{Error, List, Map, Object, SubType, UserContainer, Value};
+
+const SHORT: SubType = SubType::new(5);
+const LONG: SubType = SubType::new(300);
+
+#[test]
+fn serialize() {
+    let mut buf = vec![0; 64];
+    let mut list = List::empty_mut_with_subtype(SHORT, buf.as_mut_slice()).unwrap();
+    list.add_value(1u8).unwrap();
+
+    let mut child: Map = list
+        .add_value(Map::empty_mut_with_subtype(LONG, vec![0; 8].as_mut_slice()).unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    child.add_value(7, Value::Null).unwrap();
+
+    let expected = &[
+        0xE5, // [type] list with subtype 5
+        0x0E, // [size] container total size
+        0x02, // [count] items
+        0x20, 0x01, // uint8 (1)
+        0xF1, 0x2C, // [type] map with subtype 300
+        0x09, // [size] container total size
+        0x01, // [count] items
+        0x00, 0x00, 0x00, 0x07, // key
+        0x00, // null
+    ];
+
+    assert_eq!(list.as_bytes(), expected);
+    assert_eq!(list.subtype(), SHORT);
+}
+
+#[test]
+fn deserialize() {
+    let mut buf = vec![0; 64];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    let mut child: Object = obj
+        .add_value(
+            "user",
+            Object::empty_mut_with_subtype(LONG, vec![0; 8].as_mut_slice()).unwrap(),
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+    child.add_value("k", 5u8).unwrap();
+
+    let obj: Object = Value::deserialize(obj.as_bytes())
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let user: UserContainer = obj.get("user").unwrap().try_into().unwrap();
+    assert_eq!(user.subtype(), LONG);
+    assert_eq!(user.count(), 1);
+
+    let user = user.into_object().unwrap();
+    assert_eq!(user.get("k").unwrap(), Value::UInt8(5));
+}
+
+#[test]
+fn wrong_interpretation() {
+    let bytes = &[
+        0xE5, // [type] container with subtype 5
+        0x05, // [size] container total size
+        0x01, // [count] items
+        0x20, 0x01, // uint8 (1)
+    ];
+
+    let user: UserContainer = Value::deserialize(bytes).unwrap().try_into().unwrap();
+    assert!(matches!(user.into_map(), Err(Error::Malformed)));
+
+    let user: UserContainer = Value::deserialize(bytes).unwrap().try_into().unwrap();
+    let list = user.into_list().unwrap();
+    assert_eq!(list.get(0).unwrap(), Value::UInt8(1));
+}
+
+#[test]
+fn large() {
+    let mut buf = vec![0; 512];
+    let mut list = List::empty_mut_with_subtype(LONG, buf.as_mut_slice()).unwrap();
+
+    for i in 0..200u8 {
+        list.add_value(i).unwrap();
+    }
+
+    let bytes = list.as_bytes();
+    // [type] (2 bytes), [size] (4 bytes), [count] (4 bytes), items
+    assert_eq!(bytes.len(), 2 + 4 + 4 + 200 * 2);
+    assert_eq!(
+        &bytes[..10],
+        &[0xF1, 0x2C, 0x80, 0x00, 0x01, 0x9A, 0x80, 0x00, 0x00, 0xC8]
+    );
+
+    let user: UserContainer = Value::deserialize(bytes).unwrap().try_into().unwrap();
+    let list = user.into_list().unwrap();
+    assert_eq!(list.count(), 200);
+    assert_eq!(list.get(150).unwrap(), Value::UInt8(150));
+}
+
+#[test]
+fn reserved_subtypes() {
+    let mut buf = [0; 8];
+
+    // subtypes 0..=2 are standard list, map and object
+    for subtype in 0..=2 {
+        let subtype = SubType::new(subtype);
+        let list = List::empty_mut_with_subtype(subtype, buf.as_mut_slice());
+        assert_eq!(list.is_ok(), subtype == SubType::new(0));
+        let map = Map::empty_mut_with_subtype(subtype, buf.as_mut_slice());
+        assert_eq!(map.is_ok(), subtype == SubType::new(1));
+        let obj = Object::empty_mut_with_subtype(subtype, buf.as_mut_slice());
+        assert_eq!(obj.is_ok(), subtype == SubType::new(2));
+    }
+
+    assert!(matches!(
+        List::empty_mut_with_subtype(SubType::new(2), buf.as_mut_slice()),
+        Err(Error::TypeMismatch)
+    ));
+
+    let map = Map::empty_mut_with_subtype(SubType::new(1), buf.as_mut_slice()).unwrap();
+    assert_eq!(map.as_bytes(), Map::empty().as_bytes());
+    assert!(matches!(
+        Value::deserialize(map.as_bytes()),
+        Ok(Value::Map(_))
+    ));
+}
@@ -0,0 +1,101 @@
+use binn_rs::{BinnUserType, Error, Object, SubType, Value};
+
+#[derive(Debug, PartialEq)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl BinnUserType<'_> for Color {
+    const SUB_TYPE: SubType = SubType::new(30);
+    type Data = u32;
+
+    fn encode(&self) -> u32 {
+        u32::from_be_bytes([0, self.r, self.g, self.b])
+    }
+
+    fn decode(data: u32) -> Result<Self, Error> {
+        let [a, r, g, b] = data.to_be_bytes();
+        if a != 0 {
+            return Err(Error::Malformed);
+        }
+        Ok(Color { r, g, b })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Email<'a>(&'a str);
+
+impl<'a> BinnUserType<'a> for Email<'a> {
+    const SUB_TYPE: SubType = SubType::new(5);
+    type Data = &'a str;
+
+    fn encode(&self) -> &'a str {
+        self.0
+    }
+
+    fn decode(data: &'a str) -> Result<Self, Error> {
+        if data.contains('@') {
+            Ok(Email(data))
+        } else {
+            Err(Error::InvalidFormat)
+        }
+    }
+}
+
+#[test]
+fn add_and_decode() {
+    let mut buf = vec![0; 128];
+    let mut obj = Object::empty_mut(buf.as_mut_slice()).unwrap();
+
+    obj.add_value("color", Color { r: 1, g: 2, b: 3 }).unwrap();
+    obj.add_value("email", Email("me@example.com")).unwrap();
+
+    let expected = &[
+        0xE2, // [type] object (container)
+        0x26, // [size] container total size
+        0x02, // [count] key/value pairs
+        0x05, b'c', b'o', b'l', b'o', b'r', // key
+        0x70, 0x1E, // [type] = dword with subtype 30
+        0x00, 0x01, 0x02, 0x03, // [data]
+        0x05, b'e', b'm', b'a', b'i', b'l', // key
+        0xA5, // [type] = string with subtype 5
+        0x0E, // [size]
+        b'm', b'e', b'@', b'e', b'x', b'a', b'm', b'p', b'l', b'e', b'.', b'c', b'o', b'm',
+        0x00, // [data]
+    ];
+    assert_eq!(obj.as_bytes(), expected);
+
+    let color = obj.get("color").unwrap();
+    assert_eq!(color, Value::DWord(30.into(), 0x010203));
+    assert_eq!(
+        color.decode_user::<Color>().unwrap(),
+        Color { r: 1, g: 2, b: 3 }
+    );
+
+    let email = obj.get("email").unwrap();
+    assert_eq!(
+        email.decode_user::<Email>().unwrap(),
+        Email("me@example.com")
+    );
+}
+
+#[test]
+fn mismatch() {
+    // same storage, different subtype
+    let res = Value::DWord(31.into(), 0).decode_user::<Color>();
+    assert!(matches!(res, Err(Error::TypeMismatch)));
+
+    // same subtype, different storage
+    let res = Value::Word(30.into(), 0).decode_user::<Color>();
+    assert!(matches!(res, Err(Error::TypeMismatch)));
+
+    // not a user type
+    let res = Value::UInt32(0).decode_user::<Color>();
+    assert!(matches!(res, Err(Error::TypeMismatch)));
+
+    // decoding itself can fail
+    let res = Value::UserText(5.into(), "nobody").decode_user::<Email>();
+    assert!(matches!(res, Err(Error::InvalidFormat)));
+}
@@ -0,0 +1,250 @@
+//! WebAssembly bindings for binn-rs
+//!
+//! Provides `decode` and `encode` functions that convert between binn
+//! documents and JavaScript values:
+//!
+//! | binn                                             | JavaScript                    |
+//! |--------------------------------------------------|-------------------------------|
+//! | `Null`                                           | `null`                        |
+//! | `True`, `False`                                  | `boolean`                     |
+//! | 8, 16 and 32 bit integers, `Float`, `Double`     | `number`                      |
+//! | 64 and 128 bit integers                          | `bigint`                      |
+//! | `Text`, `DateTime`, `Date`, `Time`, `DecimalStr` | `string`                      |
+//! | `Blob`                                           | `Uint8Array`                  |
+//! | `List`                                           | `Array`                       |
+//! | `Map`                                            | `Map` with `number` keys      |
+//! | `Object`                                         | `Object`                      |
+//! | user-defined types                               | `{ subtype, storage, value }` |
+//!
+//! When encoding, integer numbers that fit into 32 bits are written with the
+//! smallest type that can hold them. Other numbers (including bigger integers
+//! and `-0`) are written as `Double`, so they are decoded back as the same
+//! `number` and not as `bigint`. Bigints are written as 64 bit integers (or 128
+//! bit ones if they don't fit), so they are decoded back as `bigint`.
+//! `undefined` is written as `Null`. User-defined values are decoded for
+//! inspection only and are encoded back as regular objects.
+#![warn(rust_2018_idioms, missing_debug_implementations, missing_docs)]
+
+use binn_rs::{Error, List, Map, Object, SubType, Value};
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Initial size of buffer for serialized containers
+const INITIAL_SIZE: usize = 256;
+
+/// Deserializes binn document into JavaScript value
+#[wasm_bindgen]
+pub fn decode(data: &[u8]) -> Result<JsValue, JsError> {
+    let value = Value::deserialize(data).map_err(binn_error)?;
+
+    Ok(to_js(value))
+}
+
+/// Serializes `Array`, `Map` or `Object` into binn document
+#[wasm_bindgen]
+pub fn encode(value: &JsValue) -> Result<Vec<u8>, JsError> {
+    serialize(value)
+}
+
+/// Error that might occur when JavaScript value is serialized
+enum SerializeError {
+    Binn(Error),
+    Js(JsError),
+}
+
+impl From<Error> for SerializeError {
+    fn from(value: Error) -> Self {
+        SerializeError::Binn(value)
+    }
+}
+
+impl From<JsError> for SerializeError {
+    fn from(value: JsError) -> Self {
+        SerializeError::Js(value)
+    }
+}
+
+impl From<JsValue> for SerializeError {
+    fn from(value: JsValue) -> Self {
+        let msg = value.as_string().unwrap_or_else(|| format!("{value:?}"));
+        SerializeError::Js(JsError::new(&msg))
+    }
+}
+
+fn binn_error(err: Error) -> JsError {
+    JsError::new(&format!("{err:?}"))
+}
+
+fn user_value(subtype: SubType, storage: &str, value: JsValue) -> JsValue {
+    let entries: Array = [
+        Array::of2(&"subtype".into(), &subtype.value().into()),
+        Array::of2(&"storage".into(), &storage.into()),
+        Array::of2(&"value".into(), &value),
+    ]
+    .iter()
+    .collect();
+
+    // entries are valid, so no error is possible
+    js_sys::Object::from_entries(&entries).unwrap().into()
+}
+
+fn to_js(value: Value<'_>) -> JsValue {
+    match value {
+        Value::Null => JsValue::NULL,
+        Value::True => JsValue::TRUE,
+        Value::False => JsValue::FALSE,
+        Value::UInt8(v) => v.into(),
+        Value::Int8(v) => v.into(),
+        Value::UInt16(v) => v.into(),
+        Value::Int16(v) => v.into(),
+        Value::UInt32(v) => v.into(),
+        Value::Int32(v) => v.into(),
+        Value::Float(v) => v.into(),
+        Value::UInt64(v) => v.into(),
+        Value::Int64(v) => v.into(),
+        Value::Double(v) => v.into(),
+        Value::Text(v)
+        | Value::DateTime(v)
+        | Value::Date(v)
+        | Value::Time(v)
+        | Value::DecimalStr(v) => v.into(),
+        Value::Blob(v) => Uint8Array::from(v).into(),
+        Value::UInt128(v) => v.into(),
+        Value::Int128(v) => v.into(),
+        Value::List(list) => list.iter().map(to_js).collect::<Array>().into(),
+        Value::Map(map) => {
+            let result = js_sys::Map::new();
+            for (key, item) in map.iter() {
+                result.set(&key.into(), &to_js(item));
+            }
+            result.into()
+        }
+        Value::Object(obj) => {
+            let entries: Array = obj
+                .iter()
+                .map(|(key, item)| Array::of2(&key.into(), &to_js(item)))
+                .collect();
+            // entries are valid, so no error is possible
+            js_sys::Object::from_entries(&entries).unwrap().into()
+        }
+        Value::Empty(sub) => user_value(sub, "nobytes", JsValue::NULL),
+        Value::Byte(sub, v) => user_value(sub, "byte", v.into()),
+        Value::Word(sub, v) => user_value(sub, "word", v.into()),
+        Value::DWord(sub, v) => user_value(sub, "dword", v.into()),
+        Value::QWord(sub, v) => user_value(sub, "qword", v.into()),
+        Value::UserText(sub, v) => user_value(sub, "string", v.into()),
+        Value::UserBlob(sub, v) => user_value(sub, "blob", Uint8Array::from(v).into()),
+        Value::UserContainer(container) => user_value(
+            container.subtype(),
+            "container",
+            Uint8Array::from(container.as_bytes()).into(),
+        ),
+    }
+}
+
+/// Serializes JavaScript container, buffer is grown until document fits
+fn serialize(value: &JsValue) -> Result<Vec<u8>, JsError> {
+    let mut buf = vec![0; INITIAL_SIZE];
+    loop {
+        match write_container(value, &mut buf) {
+            Ok(len) => {
+                buf.truncate(len);
+                return Ok(buf);
+            }
+            Err(SerializeError::Binn(Error::SmallBuffer(extra))) => {
+                let len = buf.len();
+                buf.resize(len + extra.max(len), 0);
+            }
+            Err(SerializeError::Binn(err)) => return Err(binn_error(err)),
+            Err(SerializeError::Js(err)) => return Err(err),
+        }
+    }
+}
+
+/// Writes JavaScript container into given buffer and returns written size
+fn write_container(value: &JsValue, buf: &mut [u8]) -> Result<usize, SerializeError> {
+    if let Some(array) = value.dyn_ref::<Array>() {
+        let mut list = List::empty_mut(buf)?;
+        for item in array.iter() {
+            write_item(&item, |value| list.add_value(value).map(|_| ()))?;
+        }
+        Ok(list.as_bytes().len())
+    } else if let Some(entries) = value.dyn_ref::<js_sys::Map>() {
+        let mut map = Map::empty_mut(buf)?;
+        for entry in entries.entries() {
+            let entry: Array = entry?.unchecked_into();
+            let key = entry
+                .get(0)
+                .as_f64()
+                .filter(|key| key.fract() == 0.0 && i32::try_from(*key as i64).is_ok())
+                .ok_or_else(|| JsError::new("Map keys must be 32 bit integers"))?;
+            write_item(&entry.get(1), |value| {
+                map.add_value(key as i32, value).map(|_| ())
+            })?;
+        }
+        Ok(map.as_bytes().len())
+    } else if is_plain_object(value) {
+        let mut obj = Object::empty_mut(buf)?;
+        for entry in js_sys::Object::entries(value.unchecked_ref()).iter() {
+            let entry: Array = entry.unchecked_into();
+            // keys of entries are always strings
+            let key = entry.get(0).as_string().unwrap();
+            write_item(&entry.get(1), |value| {
+                obj.add_value(&key, value).map(|_| ())
+            })?;
+        }
+        Ok(obj.as_bytes().len())
+    } else {
+        Err(JsError::new("only Array, Map or Object can be encoded as binn container").into())
+    }
+}
+
+fn is_plain_object(value: &JsValue) -> bool {
+    value.is_object() && !value.is_function() && !value.has_type::<Uint8Array>()
+}
+
+/// Converts JavaScript value to binn value and passes it to `add`
+fn write_item(
+    item: &JsValue,
+    add: impl FnOnce(Value<'_>) -> Result<(), Error>,
+) -> Result<(), SerializeError> {
+    if item.is_null() || item.is_undefined() {
+        add(Value::Null)?;
+    } else if let Some(v) = item.as_bool() {
+        add(v.into())?;
+    } else if let Some(v) = item.as_f64() {
+        // wider integers are decoded as bigint
+        let is_int32 = v.fract() == 0.0 && v >= i32::MIN as f64 && v <= u32::MAX as f64;
+        if is_int32 && !(v == 0.0 && v.is_sign_negative()) {
+            add(Value::compact_int(v as i64))?;
+        } else {
+            add(Value::Double(v))?;
+        }
+    } else if item.is_bigint() {
+        // narrower integers would be decoded as number
+        let value = match i128::try_from(item.clone()) {
+            Ok(v) => match (u64::try_from(v), i64::try_from(v)) {
+                (Ok(v), _) => Value::UInt64(v),
+                (_, Ok(v)) => Value::Int64(v),
+                _ => Value::compact_int(v),
+            },
+            Err(_) => Value::UInt128(
+                u128::try_from(item.clone())
+                    .map_err(|_| JsError::new("bigint doesn't fit into 128 bits"))?,
+            ),
+        };
+        add(value)?;
+    } else if let Some(v) = item.as_string() {
+        add(Value::Text(&v))?;
+    } else if let Some(v) = item.dyn_ref::<Uint8Array>() {
+        add(Value::Blob(&v.to_vec()))?;
+    } else if item.is_object() && !item.is_function() {
+        let data = serialize(item)?;
+        add(Value::deserialize(&data)?)?;
+    } else {
+        return Err(JsError::new("value can't be encoded as binn").into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+#![cfg(target_arch = "wasm32")]
+
+use binn_rs_wasm::{decode, encode};
+use js_sys::{Array, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn js(code: &str) -> JsValue {
+    js_sys::eval(&format!("({code})")).unwrap()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn decode_values() {
+    let data = [
+        0xE2, 0x2A, 0x05, // object with 5 items
+        0x01, b'a', 0x21, 0xFE, // int8 -2
+        0x01, b'b', 0x81, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // int64 min
+        0x01, b'c', 0xC0, 0x02, 0x01, 0x02, // blob
+        0x01, b'd', 0xE1, 0x08, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, // map {-1: false}
+        0x01, b'e', 0xE0, 0x06, 0x02, 0x00, 0x25, 0x07, // list [null, user byte]
+    ];
+
+    let obj = decode(&data).unwrap();
+    assert_eq!(get(&obj, "a"), JsValue::from(-2));
+    assert_eq!(get(&obj, "b"), JsValue::from(i64::MIN));
+    assert!(get(&obj, "b").is_bigint());
+
+    let blob: Uint8Array = get(&obj, "c").dyn_into().unwrap();
+    assert_eq!(blob.to_vec(), [1, 2]);
+
+    let map: js_sys::Map = get(&obj, "d").dyn_into().unwrap();
+    assert_eq!(map.get(&JsValue::from(-1)), JsValue::FALSE);
+
+    let list: Array = get(&obj, "e").dyn_into().unwrap();
+    assert_eq!(list.get(0), JsValue::NULL);
+    let user = list.get(1);
+    assert_eq!(get(&user, "subtype"), JsValue::from(5));
+    assert_eq!(get(&user, "storage"), JsValue::from("byte"));
+    assert_eq!(get(&user, "value"), JsValue::from(7));
+
+    assert!(decode(&[0xE2, 0x03]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn encode_values() {
+    let value = js("{ key1: false, key2: 6262 }");
+    assert_eq!(
+        encode(&value).unwrap(),
+        [
+            0xE2, 0x11, 0x02, 0x04, b'k', b'e', b'y', b'1', 0x02, 0x04, b'k', b'e', b'y', b'2',
+            0x40, 0x18, 0x76,
+        ]
+    );
+
+    let value = js("[1.5, -1, 2n ** 64n, 5n, undefined, new Uint8Array([1]), new Map([[1, 'x']])]");
+    assert_eq!(
+        encode(&value).unwrap(),
+        [
+            0xE0, 0x39, 0x07, // list
+            0x82, 0x3F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // double 1.5
+            0x21, 0xFF, // int8 -1
+            0xD0, 0x80, 0x10, // [type] uint128, [size] 16
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, // uint128 high bytes
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uint128 low bytes
+            0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, // uint64 5
+            0x00, // null
+            0xC0, 0x01, 0x01, // blob
+            0xE1, 0x0B, 0x01, 0x00, 0x00, 0x00, 0x01, 0xA0, 0x01, b'x', 0x00, // map
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn round_trip() {
+    let value = js(r#"{
+        id: 42,
+        big: -(2n ** 63n),
+        ratio: 0.25,
+        name: "пример",
+        nested: { list: [[], {}, new Map()], long: Array.from({ length: 300 }, (_, i) => i) },
+    }"#);
+
+    let data = encode(&value).unwrap();
+    let decoded = decode(&data).unwrap();
+    assert_eq!(get(&decoded, "id"), JsValue::from(42));
+    assert_eq!(get(&decoded, "big"), JsValue::from(i64::MIN));
+    assert_eq!(get(&decoded, "ratio"), JsValue::from(0.25));
+    assert_eq!(get(&decoded, "name"), JsValue::from("пример"));
+
+    let long: Array = get(&get(&decoded, "nested"), "long").dyn_into().unwrap();
+    assert_eq!(long.length(), 300);
+    assert_eq!(long.get(299), JsValue::from(299));
+
+    assert_eq!(encode(&decoded).unwrap(), data);
+}
+
+#[wasm_bindgen_test]
+fn numbers() {
+    let value: Array = js("[4294967295, -2147483648, 4294967296, -(2 ** 53), -0]").unchecked_into();
+    let data = encode(&value).unwrap();
+    assert_eq!(
+        data,
+        [
+            0xE0, 0x28, 0x05, // list
+            0x60, 0xFF, 0xFF, 0xFF, 0xFF, // uint32 max
+            0x61, 0x80, 0x00, 0x00, 0x00, // int32 min
+            0x82, 0x41, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // double 2^32
+            0x82, 0xC3, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // double -2^53
+            0x82, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // double -0
+        ]
+    );
+
+    // numbers are decoded back as numbers, even -0
+    let decoded: Array = decode(&data).unwrap().dyn_into().unwrap();
+    for (expected, actual) in value.iter().zip(decoded.iter()) {
+        assert!(actual.as_f64().is_some());
+        assert!(js_sys::Object::is(&expected, &actual));
+    }
+}
+
+#[wasm_bindgen_test]
+fn bigints() {
+    let value: Array = js("[5n, -1n, 2n ** 64n, -(2n ** 64n)]").unchecked_into();
+    let data = encode(&value).unwrap();
+    assert_eq!(
+        &data[..21],
+        [
+            0xE0, 0x3B, 0x04, // list
+            0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, // uint64 5
+            0x81, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // int64 -1
+        ]
+    );
+
+    // bigints are decoded back as bigints, even small ones
+    let decoded: Array = decode(&data).unwrap().dyn_into().unwrap();
+    for (expected, actual) in value.iter().zip(decoded.iter()) {
+        assert!(actual.is_bigint());
+        assert_eq!(expected, actual);
+    }
+}
+
+#[wasm_bindgen_test]
+fn encode_errors() {
+    assert!(encode(&js("5")).is_err());
+    assert!(encode(&js("new Map([['a', 1]])")).is_err());
+    assert!(encode(&js("[2n ** 128n]")).is_err());
+    assert!(encode(&js("[() => 1]")).is_err());
+    assert!(encode(&js(&format!("{{ {}: 1 }}", "k".repeat(256)))).is_err());
+}